@@ -0,0 +1,198 @@
+use std::{
+    collections::HashMap,
+    sync::{PoisonError, RwLock},
+};
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{ClientError, ClientResult};
+
+/// Amount of tokens, either in the smallest units of the token or in whole tokens.
+///
+/// Whole tokens are converted exactly, failing when the amount has more fractional digits than the mint has
+/// decimals or doesn't fit into `u64` units:
+///
+/// ```
+/// use solana_client_helpers::TokenAmount;
+///
+/// assert_eq!(TokenAmount::ui("12.5").to_units(6)?, 12_500_000);
+/// assert_eq!(TokenAmount::from(0.1).to_units(2)?, 10);
+/// assert_eq!(TokenAmount::units(42).to_units(9)?, 42);
+/// assert!(TokenAmount::ui("0.001").to_units(2).is_err());
+/// # Ok::<(), solana_client_helpers::ClientError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenAmount {
+    /// Amount in the smallest units of the token.
+    Units(u64),
+
+    /// Amount in whole tokens as a decimal string, e.g. `"12.5"`.
+    Ui(String),
+}
+
+impl TokenAmount {
+    pub fn units(units: u64) -> Self {
+        Self::Units(units)
+    }
+
+    /// Whole tokens given as a decimal string, which is converted without going through a float.
+    pub fn ui(amount: impl Into<String>) -> Self {
+        Self::Ui(amount.into())
+    }
+
+    /// Returns the amount in the smallest units of a token with the given decimals.
+    pub fn to_units(&self, decimals: u8) -> ClientResult<u64> {
+        match self {
+            Self::Units(units) => Ok(*units),
+            Self::Ui(amount) => ui_amount_to_units(amount, decimals),
+        }
+    }
+}
+
+impl From<&str> for TokenAmount {
+    fn from(amount: &str) -> Self {
+        Self::Ui(amount.to_string())
+    }
+}
+
+impl From<String> for TokenAmount {
+    fn from(amount: String) -> Self {
+        Self::Ui(amount)
+    }
+}
+
+/// Takes the shortest decimal representation of the float, which never has an exponent, so that `0.1` stays `"0.1"`
+/// and is converted exactly. NaN, infinite and negative amounts fail the conversion to units.
+impl From<f64> for TokenAmount {
+    fn from(amount: f64) -> Self {
+        Self::Ui(amount.to_string())
+    }
+}
+
+/// Converts a decimal amount of whole tokens like `"12.5"` into the smallest units of the token.
+pub fn ui_amount_to_units(amount: &str, decimals: u8) -> ClientResult<u64> {
+    let trimmed = amount.trim();
+    let (whole, fraction) = trimmed.split_once('.').unwrap_or((trimmed, ""));
+    let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+        return Err(ClientError::InvalidAmount(amount.to_string()));
+    }
+
+    let significant_fraction = fraction.trim_end_matches('0');
+    if significant_fraction.len() > usize::from(decimals) {
+        return Err(ClientError::AmountPrecision {
+            amount: amount.to_string(),
+            decimals,
+        });
+    }
+
+    let digits = whole
+        .bytes()
+        .chain(significant_fraction.bytes())
+        .chain(std::iter::repeat(b'0').take(usize::from(decimals) - significant_fraction.len()));
+    digits
+        .try_fold(0u64, |units, digit| {
+            units.checked_mul(10)?.checked_add(u64::from(digit - b'0'))
+        })
+        .ok_or_else(|| ClientError::AmountOverflow(amount.to_string()))
+}
+
+/// Decimals of the mints fetched by a client, which never change once a mint is initialized.
+#[derive(Debug, Default)]
+pub struct DecimalsCache {
+    decimals: RwLock<HashMap<Pubkey, u8>>,
+}
+
+impl DecimalsCache {
+    pub fn get(&self, token_mint: &Pubkey) -> Option<u8> {
+        self.decimals
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(token_mint)
+            .copied()
+    }
+
+    pub fn insert(&self, token_mint: Pubkey, decimals: u8) {
+        self.decimals
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(token_mint, decimals);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_exact_amounts() {
+        assert_eq!(ui_amount_to_units("12.5", 6).unwrap(), 12_500_000);
+        assert_eq!(ui_amount_to_units(" 3 ", 2).unwrap(), 300);
+        assert_eq!(ui_amount_to_units(".5", 1).unwrap(), 5);
+        assert_eq!(ui_amount_to_units("7.", 0).unwrap(), 7);
+        assert_eq!(ui_amount_to_units("1.2300", 2).unwrap(), 123);
+        assert_eq!(ui_amount_to_units("18446744073709551615", 0).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn never_rounds() {
+        assert!(matches!(
+            ui_amount_to_units("0.019", 2),
+            Err(ClientError::AmountPrecision { decimals: 2, .. })
+        ));
+        assert!(matches!(
+            ui_amount_to_units("1.5", 0),
+            Err(ClientError::AmountPrecision { decimals: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_overflowing_amounts() {
+        assert!(matches!(
+            ui_amount_to_units("18446744073709551616", 0),
+            Err(ClientError::AmountOverflow(_))
+        ));
+        assert!(matches!(
+            ui_amount_to_units("1", 20),
+            Err(ClientError::AmountOverflow(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        for amount in ["", ".", "abc", "1.2.3", "-1", "+1", "1e3", "1,5", "0x10"] {
+            assert!(
+                matches!(ui_amount_to_units(amount, 6), Err(ClientError::InvalidAmount(_))),
+                "{amount:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn converts_token_amounts() {
+        assert_eq!(TokenAmount::units(42).to_units(9).unwrap(), 42);
+        assert_eq!(TokenAmount::ui("0.1").to_units(2).unwrap(), 10);
+        assert_eq!(TokenAmount::from("2").to_units(3).unwrap(), 2_000);
+    }
+
+    #[test]
+    fn converts_floats_exactly() {
+        assert_eq!(TokenAmount::from(0.1).to_units(2).unwrap(), 10);
+        assert_eq!(TokenAmount::from(12.5).to_units(6).unwrap(), 12_500_000);
+        assert_eq!(TokenAmount::from(1e15).to_units(0).unwrap(), 1_000_000_000_000_000);
+        assert!(matches!(
+            TokenAmount::from(0.001).to_units(2),
+            Err(ClientError::AmountPrecision { decimals: 2, .. })
+        ));
+        assert!(matches!(
+            TokenAmount::from(1e20).to_units(0),
+            Err(ClientError::AmountOverflow(_))
+        ));
+        for amount in [f64::NAN, f64::INFINITY, -1.0] {
+            assert!(matches!(
+                TokenAmount::from(amount).to_units(2),
+                Err(ClientError::InvalidAmount(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use solana_client::{rpc_client::RpcClient, rpc_response::RpcSimulateTransactionResult};
+use solana_sdk::{
+    account::Account, clock::Slot, commitment_config::CommitmentConfig, hash::Hash, message::VersionedMessage,
+    pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction,
+};
+
+use crate::{
+    error::decode_send_error,
+    receipt::{TransactionDetails, TransactionStatus},
+    retry::rebroadcast_config,
+    simulation::Simulation,
+    ClientResult,
+};
+
+/// Calls the helpers make to the cluster, so that they can run against a validator as well as an in-process bank.
+pub trait Backend {
+    fn commitment(&self) -> CommitmentConfig;
+
+    /// Returns the RPC URL of the cluster, `None` for the backends which aren't connected to one.
+    fn url(&self) -> Option<String> {
+        None
+    }
+
+    fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> ClientResult<u64>;
+
+    /// Returns the latest blockhash together with the last block height it is valid at.
+    fn get_latest_blockhash_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<(Hash, u64)>;
+
+    fn is_blockhash_valid(&self, blockhash: &Hash, commitment: CommitmentConfig) -> ClientResult<bool>;
+
+    fn get_slot_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<Slot>;
+
+    fn get_account_with_commitment(
+        &self,
+        pubkey: &Pubkey,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<Account>>;
+
+    fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> ClientResult<Vec<Option<Account>>>;
+
+    /// Returns the prioritization fees paid recently by transactions locking the given accounts.
+    fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> ClientResult<Vec<u64>>;
+
+    /// Returns the fee the message is charged, `None` when the backend can't tell it.
+    fn get_fee_for_message(&self, _message: &VersionedMessage) -> ClientResult<Option<u64>> {
+        Ok(None)
+    }
+
+    /// Submits the transaction without waiting for its confirmation, decoding the errors of its preflight checks.
+    fn send_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature>;
+
+    /// Submits the already sent transaction once more, skipping the preflight checks.
+    fn resend_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature>;
+
+    /// Returns the status of the transaction once it has reached the commitment, `None` until then.
+    fn get_signature_status_with_commitment(
+        &self,
+        signature: &Signature,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionStatus>>;
+
+    /// Returns the fee, the consumed compute units and the logs of the processed transaction, `None` when the
+    /// backend doesn't serve them.
+    fn get_transaction_details(
+        &self,
+        _signature: &Signature,
+        _commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionDetails>> {
+        Ok(None)
+    }
+
+    /// Simulates the transaction, returning the state of the given accounts after it when the backend supports it.
+    fn simulate_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        accounts: &[Pubkey],
+    ) -> ClientResult<RpcSimulateTransactionResult>;
+
+    /// Funds the account and waits for the transfer to be confirmed.
+    fn airdrop(&self, to_pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature>;
+}
+
+impl Backend for RpcClient {
+    fn commitment(&self) -> CommitmentConfig {
+        RpcClient::commitment(self)
+    }
+
+    fn url(&self) -> Option<String> {
+        Some(RpcClient::url(self))
+    }
+
+    fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> ClientResult<u64> {
+        Ok(RpcClient::get_minimum_balance_for_rent_exemption(self, data_len)?)
+    }
+
+    fn get_latest_blockhash_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<(Hash, u64)> {
+        Ok(RpcClient::get_latest_blockhash_with_commitment(self, commitment)?)
+    }
+
+    fn is_blockhash_valid(&self, blockhash: &Hash, commitment: CommitmentConfig) -> ClientResult<bool> {
+        Ok(RpcClient::is_blockhash_valid(self, blockhash, commitment)?)
+    }
+
+    fn get_slot_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<Slot> {
+        Ok(RpcClient::get_slot_with_commitment(self, commitment)?)
+    }
+
+    fn get_account_with_commitment(
+        &self,
+        pubkey: &Pubkey,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<Account>> {
+        Ok(RpcClient::get_account_with_commitment(self, pubkey, commitment)?.value)
+    }
+
+    fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        Ok(RpcClient::get_multiple_accounts(self, pubkeys)?)
+    }
+
+    fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> ClientResult<Vec<u64>> {
+        Ok(RpcClient::get_recent_prioritization_fees(self, accounts)?
+            .into_iter()
+            .map(|fee| fee.prioritization_fee)
+            .collect())
+    }
+
+    fn get_fee_for_message(&self, message: &VersionedMessage) -> ClientResult<Option<u64>> {
+        let fee = match message {
+            VersionedMessage::Legacy(message) => RpcClient::get_fee_for_message(self, message)?,
+            VersionedMessage::V0(message) => RpcClient::get_fee_for_message(self, message)?,
+        };
+        Ok(Some(fee))
+    }
+
+    fn send_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        RpcClient::send_transaction(self, transaction).map_err(|error| decode_send_error(error, transaction))
+    }
+
+    fn resend_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        Ok(RpcClient::send_transaction_with_config(
+            self,
+            transaction,
+            rebroadcast_config(),
+        )?)
+    }
+
+    fn get_signature_status_with_commitment(
+        &self,
+        signature: &Signature,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionStatus>> {
+        let status = RpcClient::get_signature_statuses(self, &[*signature])?
+            .value
+            .pop()
+            .flatten();
+        Ok(status.filter(|status| status.satisfies_commitment(commitment)))
+    }
+
+    fn get_transaction_details(
+        &self,
+        signature: &Signature,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionDetails>> {
+        let transaction =
+            RpcClient::get_transaction_with_config(self, signature, TransactionDetails::config(commitment))?;
+        Ok(transaction.transaction.meta.map(TransactionDetails::from))
+    }
+
+    fn simulate_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        accounts: &[Pubkey],
+    ) -> ClientResult<RpcSimulateTransactionResult> {
+        let config = Simulation::config(accounts, RpcClient::commitment(self));
+        Ok(RpcClient::simulate_transaction_with_config(self, transaction, config)?.value)
+    }
+
+    fn airdrop(&self, to_pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature> {
+        let blockhash = RpcClient::get_latest_blockhash(self)?;
+        let signature = RpcClient::request_airdrop_with_blockhash(self, to_pubkey, lamports, &blockhash)?;
+        RpcClient::confirm_transaction_with_spinner(self, &signature, &blockhash, RpcClient::commitment(self))?;
+
+        Ok(signature)
+    }
+}
@@ -0,0 +1,348 @@
+//! Backends running the helpers against an in-process bank of `solana-program-test`.
+//!
+//! ```ignore
+//! use solana_client_helpers::{banks::AsyncBanksBackend, nonblocking::{AsyncClient, AsyncSplToken}};
+//! use solana_program_test::ProgramTest;
+//!
+//! let (banks_client, payer, _) = ProgramTest::default().start().await;
+//! let client = AsyncClient::new(AsyncBanksBackend::new(banks_client, payer.insecure_clone()), payer);
+//! let token_mint = client.create_token_mint(&client.payer_pubkey(), 2).await?;
+//! ```
+
+use async_trait::async_trait;
+use solana_banks_client::{BanksClient, BanksClientError};
+use solana_banks_interface::TransactionConfirmationStatus as BanksConfirmationStatus;
+use solana_client::rpc_response::RpcSimulateTransactionResult;
+use solana_sdk::{
+    account::Account,
+    clock::Slot,
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    message::{Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    system_instruction,
+    transaction::{Transaction, VersionedTransaction},
+};
+use tokio::runtime::Runtime;
+
+use crate::{
+    backend::Backend,
+    nonblocking::backend::AsyncBackend,
+    receipt::{TransactionConfirmationStatus, TransactionStatus},
+    ClientResult,
+};
+
+/// Async backend on top of a [`BanksClient`].
+///
+/// The bank executes transactions right away, so their statuses are reported regardless of the commitment, and
+/// airdrops are transfers from the faucet keypair, usually the payer returned by `ProgramTest::start`.
+pub struct AsyncBanksBackend {
+    client: BanksClient,
+    faucet: Keypair,
+    commitment: CommitmentConfig,
+}
+
+impl AsyncBanksBackend {
+    pub fn new(client: BanksClient, faucet: Keypair) -> Self {
+        Self {
+            client,
+            faucet,
+            commitment: CommitmentConfig::processed(),
+        }
+    }
+
+    pub fn with_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// The calls of [`BanksClient`] take it mutably, while cloning it only clones the channel to the bank.
+    fn banks(&self) -> BanksClient {
+        self.client.clone()
+    }
+}
+
+#[async_trait]
+impl AsyncBackend for AsyncBanksBackend {
+    fn commitment(&self) -> CommitmentConfig {
+        self.commitment
+    }
+
+    async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> ClientResult<u64> {
+        Ok(self.banks().get_rent().await?.minimum_balance(data_len))
+    }
+
+    async fn get_latest_blockhash_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<(Hash, u64)> {
+        Ok(self
+            .banks()
+            .get_latest_blockhash_with_commitment(commitment.commitment)
+            .await?
+            .ok_or(BanksClientError::ClientError("valid blockhash not found"))?)
+    }
+
+    async fn is_blockhash_valid(&self, blockhash: &Hash, _commitment: CommitmentConfig) -> ClientResult<bool> {
+        // The fee of a message is only known while its blockhash is valid.
+        let message = Message::new_with_blockhash(&[], None, blockhash);
+        Ok(self.banks().get_fee_for_message(message).await?.is_some())
+    }
+
+    async fn get_slot_with_commitment(&self, _commitment: CommitmentConfig) -> ClientResult<Slot> {
+        Ok(self.banks().get_root_slot().await?)
+    }
+
+    async fn get_account_with_commitment(
+        &self,
+        pubkey: &Pubkey,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<Account>> {
+        Ok(self
+            .banks()
+            .get_account_with_commitment(*pubkey, commitment.commitment)
+            .await?)
+    }
+
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        let mut accounts = Vec::with_capacity(pubkeys.len());
+        for pubkey in pubkeys {
+            accounts.push(self.get_account_with_commitment(pubkey, self.commitment).await?);
+        }
+        Ok(accounts)
+    }
+
+    async fn get_recent_prioritization_fees(&self, _accounts: &[Pubkey]) -> ClientResult<Vec<u64>> {
+        Ok(Vec::new())
+    }
+
+    async fn get_fee_for_message(&self, message: &VersionedMessage) -> ClientResult<Option<u64>> {
+        // The bank only prices legacy messages.
+        match message {
+            VersionedMessage::Legacy(message) => Ok(self.banks().get_fee_for_message(message.clone()).await?),
+            VersionedMessage::V0(_) => Ok(None),
+        }
+    }
+
+    async fn send_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        self.banks().send_transaction(transaction.clone()).await?;
+        Ok(transaction.signatures[0])
+    }
+
+    async fn resend_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        self.send_transaction(transaction).await
+    }
+
+    async fn get_signature_status_with_commitment(
+        &self,
+        signature: &Signature,
+        _commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionStatus>> {
+        Ok(self
+            .banks()
+            .get_transaction_status(*signature)
+            .await?
+            .map(|status| TransactionStatus {
+                slot: status.slot,
+                confirmations: status.confirmations,
+                status: status.err.clone().map_or(Ok(()), Err),
+                err: status.err,
+                confirmation_status: status.confirmation_status.map(|status| match status {
+                    BanksConfirmationStatus::Processed => TransactionConfirmationStatus::Processed,
+                    BanksConfirmationStatus::Confirmed => TransactionConfirmationStatus::Confirmed,
+                    BanksConfirmationStatus::Finalized => TransactionConfirmationStatus::Finalized,
+                }),
+            }))
+    }
+
+    async fn simulate_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        _accounts: &[Pubkey],
+    ) -> ClientResult<RpcSimulateTransactionResult> {
+        let simulation = self.banks().simulate_transaction(transaction.clone()).await?;
+        let details = simulation.simulation_details;
+
+        Ok(RpcSimulateTransactionResult {
+            err: simulation.result.and_then(Result::err),
+            logs: details.as_ref().map(|details| details.logs.clone()),
+            accounts: None,
+            units_consumed: details.map(|details| details.units_consumed),
+            return_data: None,
+        })
+    }
+
+    async fn airdrop(&self, to_pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature> {
+        let mut banks = self.banks();
+        let blockhash = banks.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(&self.faucet.pubkey(), to_pubkey, lamports)],
+            Some(&self.faucet.pubkey()),
+            &[&self.faucet],
+            blockhash,
+        );
+        let signature = transaction.signatures[0];
+        banks.process_transaction(transaction).await?;
+
+        Ok(signature)
+    }
+}
+
+/// Blocking backend on top of a [`BanksClient`], driving it by the runtime the bank was started on.
+pub struct BanksBackend {
+    backend: AsyncBanksBackend,
+    runtime: Runtime,
+}
+
+impl BanksBackend {
+    /// ```ignore
+    /// let runtime = Runtime::new()?;
+    /// let (banks_client, payer, _) = runtime.block_on(ProgramTest::default().start());
+    /// let backend = BanksBackend::new(runtime, AsyncBanksBackend::new(banks_client, payer.insecure_clone()));
+    /// let client = Client::new(backend, payer);
+    /// ```
+    pub fn new(runtime: Runtime, backend: AsyncBanksBackend) -> Self {
+        Self { backend, runtime }
+    }
+}
+
+impl Backend for BanksBackend {
+    fn commitment(&self) -> CommitmentConfig {
+        self.backend.commitment
+    }
+
+    fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> ClientResult<u64> {
+        self.runtime
+            .block_on(self.backend.get_minimum_balance_for_rent_exemption(data_len))
+    }
+
+    fn get_latest_blockhash_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<(Hash, u64)> {
+        self.runtime
+            .block_on(self.backend.get_latest_blockhash_with_commitment(commitment))
+    }
+
+    fn is_blockhash_valid(&self, blockhash: &Hash, commitment: CommitmentConfig) -> ClientResult<bool> {
+        self.runtime
+            .block_on(self.backend.is_blockhash_valid(blockhash, commitment))
+    }
+
+    fn get_slot_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<Slot> {
+        self.runtime.block_on(self.backend.get_slot_with_commitment(commitment))
+    }
+
+    fn get_account_with_commitment(
+        &self,
+        pubkey: &Pubkey,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<Account>> {
+        self.runtime
+            .block_on(self.backend.get_account_with_commitment(pubkey, commitment))
+    }
+
+    fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        self.runtime.block_on(self.backend.get_multiple_accounts(pubkeys))
+    }
+
+    fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> ClientResult<Vec<u64>> {
+        self.runtime
+            .block_on(self.backend.get_recent_prioritization_fees(accounts))
+    }
+
+    fn get_fee_for_message(&self, message: &VersionedMessage) -> ClientResult<Option<u64>> {
+        self.runtime.block_on(self.backend.get_fee_for_message(message))
+    }
+
+    fn send_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        self.runtime.block_on(self.backend.send_transaction(transaction))
+    }
+
+    fn resend_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        self.runtime.block_on(self.backend.resend_transaction(transaction))
+    }
+
+    fn get_signature_status_with_commitment(
+        &self,
+        signature: &Signature,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionStatus>> {
+        self.runtime
+            .block_on(self.backend.get_signature_status_with_commitment(signature, commitment))
+    }
+
+    fn simulate_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        accounts: &[Pubkey],
+    ) -> ClientResult<RpcSimulateTransactionResult> {
+        self.runtime
+            .block_on(self.backend.simulate_transaction(transaction, accounts))
+    }
+
+    fn airdrop(&self, to_pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature> {
+        self.runtime.block_on(self.backend.airdrop(to_pubkey, lamports))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program_test::ProgramTest;
+
+    use super::*;
+    use crate::{
+        nonblocking::{AsyncClient, AsyncSplToken},
+        Client, SplToken,
+    };
+
+    #[tokio::test]
+    async fn async_backend_processes_token_transactions() {
+        let (banks_client, payer, _) = ProgramTest::default().start().await;
+        let client = AsyncClient::new(AsyncBanksBackend::new(banks_client, payer.insecure_clone()), payer);
+        let owner = Keypair::new();
+
+        let token_mint = client.create_token_mint(&owner.pubkey(), 2).await.unwrap();
+        let account = client
+            .create_token_account(&owner.pubkey(), &token_mint.pubkey())
+            .await
+            .unwrap();
+        let receipt = client
+            .mint_to(&owner, &token_mint.pubkey(), &account.pubkey(), 1_250, 2)
+            .await
+            .unwrap();
+
+        assert!(receipt.slot.is_some());
+        let token_account = client.get_token_account(&account.pubkey()).await.unwrap();
+        assert_eq!(token_account.amount, 1_250);
+        assert_eq!(token_account.owner, owner.pubkey());
+    }
+
+    #[test]
+    fn blocking_backend_processes_token_transactions() {
+        let runtime = Runtime::new().unwrap();
+        let (banks_client, payer, _) = runtime.block_on(ProgramTest::default().start());
+        let backend = BanksBackend::new(runtime, AsyncBanksBackend::new(banks_client, payer.insecure_clone()));
+        let client = Client::new(backend, payer);
+        let (owner, recipient) = (Keypair::new(), Keypair::new());
+
+        let token_mint = client.create_token_mint(&owner.pubkey(), 2).unwrap();
+        let source = client
+            .create_token_account(&owner.pubkey(), &token_mint.pubkey())
+            .unwrap();
+        let destination = client
+            .create_token_account(&recipient.pubkey(), &token_mint.pubkey())
+            .unwrap();
+        client
+            .mint_to(&owner, &token_mint.pubkey(), &source.pubkey(), 1_250, 2)
+            .unwrap();
+        client
+            .transfer_to(
+                &owner,
+                &token_mint.pubkey(),
+                &source.pubkey(),
+                &destination.pubkey(),
+                250,
+                2,
+            )
+            .unwrap();
+
+        assert_eq!(client.get_token_account(&source.pubkey()).unwrap().amount, 1_000);
+        assert_eq!(client.get_token_account(&destination.pubkey()).unwrap().amount, 250);
+    }
+}
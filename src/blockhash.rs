@@ -0,0 +1,75 @@
+use std::{
+    sync::{PoisonError, RwLock},
+    time::Instant,
+};
+
+use solana_sdk::{
+    clock::{DEFAULT_MS_PER_SLOT, MAX_PROCESSING_AGE},
+    hash::Hash,
+};
+
+/// Latest blockhash shared by all the transactions of a client until it gets close to expiry, enabled with
+/// [`Client::with_blockhash_cache`](crate::Client::with_blockhash_cache).
+///
+/// The current block height is estimated from the time passed since the blockhash was fetched, so the cache doesn't
+/// cost any extra RPC requests. Note that identical transactions signed with the same blockhash have the same
+/// signature, so only one of them lands.
+#[derive(Debug)]
+pub struct BlockhashCache {
+    refresh_margin: u64,
+    cached: RwLock<Option<CachedBlockhash>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedBlockhash {
+    pub blockhash: Hash,
+    pub last_valid_block_height: u64,
+    pub fetched_at: Instant,
+}
+
+impl CachedBlockhash {
+    pub fn estimated_block_height(&self) -> u64 {
+        let fetched_block_height = self.last_valid_block_height.saturating_sub(MAX_PROCESSING_AGE as u64);
+        let elapsed_blocks = self.fetched_at.elapsed().as_millis() as u64 / DEFAULT_MS_PER_SLOT;
+        fetched_block_height + elapsed_blocks
+    }
+}
+
+impl Default for BlockhashCache {
+    fn default() -> Self {
+        Self::new(MAX_PROCESSING_AGE as u64 / 2)
+    }
+}
+
+impl BlockhashCache {
+    /// Creates a cache refreshing the blockhash `refresh_margin` blocks before its last valid block height.
+    pub fn new(refresh_margin: u64) -> Self {
+        Self {
+            refresh_margin,
+            cached: RwLock::new(None),
+        }
+    }
+
+    /// Returns the cached blockhash, unless it is missing or close to expiry.
+    pub fn get(&self) -> Option<Hash> {
+        self.cached()
+            .filter(|cached| cached.estimated_block_height() + self.refresh_margin < cached.last_valid_block_height)
+            .map(|cached| cached.blockhash)
+    }
+
+    pub fn cached(&self) -> Option<CachedBlockhash> {
+        *self.cached.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn update(&self, blockhash: Hash, last_valid_block_height: u64) {
+        *self.cached.write().unwrap_or_else(PoisonError::into_inner) = Some(CachedBlockhash {
+            blockhash,
+            last_valid_block_height,
+            fetched_at: Instant::now(),
+        });
+    }
+
+    pub fn invalidate(&self) {
+        *self.cached.write().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
@@ -1,15 +1,48 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    io, iter,
+    ops::{Deref, DerefMut},
+    path::PathBuf,
+    sync::Arc,
+    thread,
+    time::Instant,
+};
 
+use solana_cli_config::Config;
 pub use solana_client::{client_error, rpc_client::RpcClient};
+use solana_client::{nonce_utils, pubsub_client::PubsubClientError};
 use solana_sdk::{
+    address_lookup_table_account::AddressLookupTableAccount,
+    commitment_config::CommitmentConfig,
     hash::Hash,
+    instruction::Instruction,
+    message::{v0, CompileError, Message, VersionedMessage},
     program_error::ProgramError,
     pubkey::Pubkey,
+    sanitize::SanitizeError,
     signature::{Keypair, Signature, Signer},
-    system_instruction,
-    transaction::Transaction,
+    signer::SignerError,
+    transaction::{Transaction, VersionedTransaction},
 };
 use thiserror::Error;
+use tracing::instrument;
+
+#[cfg(feature = "banks")]
+use solana_banks_client::BanksClientError;
+
+use crate::{
+    amount::DecimalsCache,
+    backend::Backend,
+    blockhash::BlockhashCache,
+    compute_budget::{fee_percentile, ComputeBudget, PriorityFee},
+    confirmation::{confirmed_status, Confirmation, PendingTransaction},
+    error::SplError,
+    instructions,
+    nonce::{nonce_account_of, DurableNonceConfig, SystemNonce},
+    receipt::{ReceiptLog, TransactionReceipt, TransactionStatus},
+    retry::{RetryDecision, RetryPolicy, STATUS_POLL_INTERVAL},
+    simulation::{Simulation, SimulationLog},
+    telemetry::TransactionTelemetry,
+};
 
 #[derive(Debug, Error)]
 pub enum ClientError {
@@ -18,33 +51,643 @@ pub enum ClientError {
 
     #[error(transparent)]
     Program(#[from] ProgramError),
+
+    #[error(transparent)]
+    Signer(#[from] SignerError),
+
+    #[cfg(feature = "banks")]
+    #[error(transparent)]
+    Banks(#[from] BanksClientError),
+
+    #[error("transaction simulation failed: {error:?}", error = .0.error)]
+    Simulation(Box<Simulation>),
+
+    #[error(transparent)]
+    Nonce(#[from] nonce_utils::Error),
+
+    #[error(transparent)]
+    Pubsub(#[from] PubsubClientError),
+
+    #[error(transparent)]
+    Compile(#[from] CompileError),
+
+    #[error("failed to decode transaction: {0}")]
+    TransactionEncoding(#[from] bincode::Error),
+
+    #[error("invalid transaction: {0}")]
+    InvalidTransaction(#[from] SanitizeError),
+
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+
+    #[error("account {0} is not an address lookup table")]
+    InvalidLookupTable(Pubkey),
+
+    #[error("account {account} is owned by {owner} instead of {expected}")]
+    InvalidOwner {
+        account: Pubkey,
+        owner: Pubkey,
+        expected: Pubkey,
+    },
+
+    #[error("account {0} is not initialized")]
+    UninitializedAccount(Pubkey),
+
+    #[error("account {0} has invalid data")]
+    InvalidAccountData(Pubkey),
+
+    #[error("invalid token amount {0:?}")]
+    InvalidAmount(String),
+
+    #[error("token amount {amount} has more than {decimals} decimals")]
+    AmountPrecision { amount: String, decimals: u8 },
+
+    #[error("token amount {0} is too large")]
+    AmountOverflow(String),
+
+    #[error("failed to load config {}: {error}", .path.display())]
+    Config { path: PathBuf, error: io::Error },
+
+    #[error("failed to read keypair {}: {message}", .path.display())]
+    Keypair { path: PathBuf, message: String },
+
+    #[error("invalid commitment {0:?}")]
+    InvalidCommitment(String),
+
+    #[error("payer is not set")]
+    MissingPayer,
+
+    #[error("websocket URL is not set")]
+    MissingWebsocketUrl,
+
+    #[error("no RPC endpoint is given")]
+    MissingEndpoints,
+
+    #[error("subscription closed")]
+    SubscriptionClosed,
+
+    #[error("transaction is missing signatures of {0:?}")]
+    MissingSignatures(Vec<Pubkey>),
+
+    #[error("transaction {0} expired before it was confirmed")]
+    Expired(Signature),
+
+    #[error("instruction {index} failed: {error}")]
+    Instruction {
+        index: u8,
+        error: SplError,
+        logs: Vec<String>,
+    },
+
+    /// A batch of a [`TransactionBuilder`](crate::TransactionBuilder) failed after the previous batches were sent,
+    /// whose receipts are kept.
+    #[error("transaction {} of the batch failed: {error}", .sent.len() + 1)]
+    PartialBatch {
+        sent: Vec<TransactionReceipt>,
+        error: Box<ClientError>,
+    },
 }
 
 pub type ClientResult<T> = Result<T, ClientError>;
 
-pub struct Client {
-    pub client: RpcClient,
-    pub payer: Keypair,
+/// Client running the helpers against the backend, which is an [`RpcClient`] connected to a cluster by default.
+pub struct Client<B = RpcClient> {
+    pub client: B,
+    pub payer: Box<dyn Signer + Send + Sync>,
+    pub compute_budget: ComputeBudget,
+    pub simulation: Option<SimulationLog>,
+    pub retry_policy: RetryPolicy,
+    pub blockhash_cache: Option<Arc<BlockhashCache>>,
+    pub decimals_cache: Arc<DecimalsCache>,
+    pub durable_nonce: Option<DurableNonceConfig>,
+    pub websocket_url: Option<String>,
+    pub confirmation: Confirmation,
+    pub receipt_details: bool,
+    pub receipt_log: Option<ReceiptLog>,
 }
 
-impl Client {
-    pub fn payer(&self) -> &Keypair {
-        &self.payer
+impl<B: Backend> Client<B> {
+    /// Creates a client paying for transactions with the given signer, which may be any [`Signer`] implementation
+    /// such as a keypair, a presigner or a remote wallet.
+    ///
+    /// ```
+    /// use solana_client_helpers::{Client, RpcClient};
+    /// use solana_sdk::{
+    ///     pubkey::Pubkey,
+    ///     signature::{Keypair, Signature, Signer},
+    ///     signer::SignerError,
+    /// };
+    ///
+    /// // A local stand-in for a signer which keeps its key out of the process.
+    /// struct RemoteSigner(Keypair);
+    ///
+    /// impl Signer for RemoteSigner {
+    ///     fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+    ///         self.0.try_pubkey()
+    ///     }
+    ///
+    ///     fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+    ///         self.0.try_sign_message(message)
+    ///     }
+    ///
+    ///     fn is_interactive(&self) -> bool {
+    ///         false
+    ///     }
+    /// }
+    ///
+    /// let keypair = Keypair::new();
+    /// let pubkey = keypair.pubkey();
+    /// let client = Client::new(RpcClient::new("http://localhost:8899".into()), RemoteSigner(keypair));
+    ///
+    /// assert_eq!(client.payer_pubkey(), pubkey);
+    /// let signature = client.payer().sign_message(b"message");
+    /// assert!(signature.verify(pubkey.as_ref(), b"message"));
+    /// ```
+    pub fn new(client: B, payer: impl Signer + Send + Sync + 'static) -> Self {
+        Self::with_boxed_payer(client, Box::new(payer))
+    }
+
+    pub(crate) fn with_boxed_payer(client: B, payer: Box<dyn Signer + Send + Sync>) -> Self {
+        Self {
+            client,
+            payer,
+            compute_budget: ComputeBudget::default(),
+            simulation: None,
+            retry_policy: RetryPolicy::default(),
+            blockhash_cache: None,
+            decimals_cache: Arc::default(),
+            durable_nonce: None,
+            websocket_url: None,
+            confirmation: Confirmation::default(),
+            receipt_details: false,
+            receipt_log: None,
+        }
+    }
+
+    /// Sets the compute budget requested for every transaction built by the helpers.
+    pub fn with_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+
+    /// Sets the retry strategy applied to every transaction sent by the client.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Serves the latest blockhash from the given cache, which may be shared with another client connected to the
+    /// same cluster, saving a request per transaction.
+    ///
+    /// Identical transactions signed with the same blockhash have the same signature, so only the first of them lands
+    /// while the blockhash stays cached. Clients repeating transactions, e.g. the same transfer, shouldn't use it.
+    pub fn with_blockhash_cache(mut self, blockhash_cache: Arc<BlockhashCache>) -> Self {
+        self.blockhash_cache = Some(blockhash_cache);
+        self
+    }
+
+    /// Fetches a fresh blockhash for every transaction, which is the default.
+    pub fn without_blockhash_cache(mut self) -> Self {
+        self.blockhash_cache = None;
+        self
+    }
+
+    /// Shares the given cache of mint decimals, e.g. with another client connected to the same cluster.
+    pub fn with_decimals_cache(mut self, decimals_cache: Arc<DecimalsCache>) -> Self {
+        self.decimals_cache = decimals_cache;
+        self
+    }
+
+    /// Builds every transaction against the durable nonce instead of the latest blockhash.
+    pub fn with_durable_nonce(mut self, durable_nonce: DurableNonceConfig) -> Self {
+        self.durable_nonce = Some(durable_nonce);
+        self
+    }
+
+    /// Sets the pubsub endpoint of the subscriptions, which is derived from the RPC URL of the backend otherwise.
+    pub fn with_websocket_url(mut self, websocket_url: impl Into<String>) -> Self {
+        self.websocket_url = Some(websocket_url.into());
+        self
+    }
+
+    /// Returns the pubsub endpoint of the subscriptions.
+    pub fn websocket_url(&self) -> ClientResult<String> {
+        if let Some(websocket_url) = &self.websocket_url {
+            return Ok(websocket_url.clone());
+        }
+        let url = self.client.url().ok_or(ClientError::MissingWebsocketUrl)?;
+        Ok(Config::compute_websocket_url(&url))
+    }
+
+    /// Sets how long every transaction sent by the client is waited for, the commitment of the backend by default.
+    pub fn with_confirmation(mut self, confirmation: Confirmation) -> Self {
+        self.confirmation = confirmation;
+        self
+    }
+
+    /// Switches the client into the simulation mode, where transactions are simulated instead of being sent.
+    ///
+    /// Every simulation is recorded and can be inspected with [`Client::take_simulations`]. Note that state changes
+    /// of a simulated transaction are not visible to the following ones, so multi-transaction helpers like
+    /// `create_swap_and_init` are only validated up to the first transaction depending on a previous one.
+    pub fn with_simulation(mut self) -> Self {
+        self.simulation = Some(SimulationLog::default());
+        self
+    }
+
+    pub fn is_simulation(&self) -> bool {
+        self.simulation.is_some()
+    }
+
+    /// Returns the simulations recorded since the previous call.
+    pub fn take_simulations(&self) -> Vec<Simulation> {
+        self.simulation.as_ref().map(SimulationLog::take).unwrap_or_default()
+    }
+
+    /// Fetches the fee, the consumed compute units and the logs of every confirmed transaction into its receipt, at
+    /// the cost of another request per transaction.
+    pub fn with_receipt_details(mut self) -> Self {
+        self.receipt_details = true;
+        self
+    }
+
+    /// Records the receipt of every transaction sent by the client, to be collected with
+    /// [`Client::take_receipts`].
+    pub fn with_receipt_log(mut self) -> Self {
+        self.receipt_log = Some(ReceiptLog::default());
+        self
+    }
+
+    /// Returns the receipts recorded since the previous call.
+    pub fn take_receipts(&self) -> Vec<TransactionReceipt> {
+        self.receipt_log.as_ref().map(ReceiptLog::take).unwrap_or_default()
+    }
+
+    pub fn payer(&self) -> &dyn Signer {
+        self.payer.as_ref()
     }
 
     pub fn payer_pubkey(&self) -> Pubkey {
         self.payer.pubkey()
     }
 
+    /// Returns the latest blockhash, served from the cache, if any, until it gets close to expiry.
     pub fn latest_blockhash(&self) -> ClientResult<Hash> {
-        Ok(self.client.get_latest_blockhash()?)
+        if let Some(blockhash) = self.blockhash_cache.as_ref().and_then(|cache| cache.get()) {
+            return Ok(blockhash);
+        }
+
+        let (blockhash, last_valid_block_height) = self.get_latest_blockhash_with_commitment(self.commitment())?;
+        if let Some(cache) = &self.blockhash_cache {
+            cache.update(blockhash, last_valid_block_height);
+        }
+
+        Ok(blockhash)
+    }
+
+    pub fn process_transaction(&self, transaction: &Transaction) -> ClientResult<TransactionReceipt> {
+        self.process_versioned_transaction(&transaction.clone().into())
+    }
+
+    pub fn process_versioned_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> ClientResult<TransactionReceipt> {
+        self.process_transaction_with_signers(transaction, None, self.confirmation)
+    }
+
+    /// Same as [`Client::process_versioned_transaction`], but overrides the confirmation of the client.
+    pub fn process_transaction_with_confirmation(
+        &self,
+        transaction: &VersionedTransaction,
+        confirmation: Confirmation,
+    ) -> ClientResult<TransactionReceipt> {
+        self.process_transaction_with_signers(transaction, None, confirmation)
+    }
+
+    /// Sends the transaction according to the retry policy, re-signing it by the payer and the given signers when
+    /// its blockhash expires, and waits for it as long as the confirmation requires.
+    pub(crate) fn process_transaction_with_signers(
+        &self,
+        transaction: &VersionedTransaction,
+        signers: Option<&[&dyn Signer]>,
+        confirmation: Confirmation,
+    ) -> ClientResult<TransactionReceipt> {
+        if let Some(log) = &self.simulation {
+            let simulation = self.simulate(transaction)?;
+            return log
+                .record_result(simulation)
+                .map(|receipt| self.record_receipt(receipt));
+        }
+
+        let mut telemetry = TransactionTelemetry::start(transaction);
+        let span = telemetry.span().clone();
+        let _entered = span.enter();
+
+        let mut transaction = transaction.clone();
+        let commitment = confirmation.commitment(self.commitment());
+        let result = self
+            .send_with_retries(&mut transaction, signers, commitment, &mut telemetry)
+            .map(|status| self.receipt(&transaction, status.as_ref()));
+        let fee = result.as_ref().ok().and_then(|receipt| receipt.fee);
+        telemetry.finish(&result, fee);
+        result.map(|receipt| self.record_receipt(receipt))
+    }
+
+    /// Sends the transaction according to the retry policy like [`Client::process_versioned_transaction`], but
+    /// returns without waiting for its confirmation, which is left to the returned handle.
+    pub fn submit_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<PendingTransaction<'_, B>> {
+        self.submit_transaction_with_signers(transaction, None)
+    }
+
+    pub(crate) fn submit_transaction_with_signers(
+        &self,
+        transaction: &VersionedTransaction,
+        signers: Option<&[&dyn Signer]>,
+    ) -> ClientResult<PendingTransaction<'_, B>> {
+        if self.is_simulation() {
+            let receipt = self.process_transaction_with_signers(transaction, signers, Confirmation::Sent)?;
+            return Ok(PendingTransaction::resolved(self, transaction.clone(), receipt));
+        }
+
+        let mut telemetry = TransactionTelemetry::start(transaction);
+        let span = telemetry.span().clone();
+        let _entered = span.enter();
+
+        let mut transaction = transaction.clone();
+        match self.send_with_retries(&mut transaction, signers, None, &mut telemetry) {
+            Ok(_) => Ok(PendingTransaction::new(self, transaction, self.commitment(), telemetry)),
+            Err(error) => {
+                let result = Err(error);
+                telemetry.finish(&result, None);
+                result
+            },
+        }
+    }
+
+    /// Makes the receipt of the sent transaction, fetching the details of a confirmed one if the client is
+    /// configured so.
+    pub(crate) fn receipt(
+        &self,
+        transaction: &VersionedTransaction,
+        status: Option<&TransactionStatus>,
+    ) -> TransactionReceipt {
+        let signature = transaction.signatures[0];
+        let details = match status {
+            Some(_) if self.receipt_details => self
+                .get_transaction_details(&signature, self.commitment())
+                .ok()
+                .flatten(),
+            _ => None,
+        };
+        TransactionReceipt::assemble(signature, status, details)
+    }
+
+    pub(crate) fn record_receipt(&self, receipt: TransactionReceipt) -> TransactionReceipt {
+        if let Some(log) = &self.receipt_log {
+            log.record(receipt.clone());
+        }
+        receipt
+    }
+
+    fn send_with_retries(
+        &self,
+        transaction: &mut VersionedTransaction,
+        signers: Option<&[&dyn Signer]>,
+        commitment: Option<CommitmentConfig>,
+        telemetry: &mut TransactionTelemetry,
+    ) -> ClientResult<Option<TransactionStatus>> {
+        let mut retry = 0;
+        loop {
+            let error = match self.send_and_confirm_attempt(transaction, retry > 0, commitment) {
+                Ok(status) => return Ok(status),
+                Err(error) => error,
+            };
+
+            retry += 1;
+            match (self.retry_policy.decide(retry, &error, signers.is_some()), signers) {
+                (RetryDecision::Retry, _) => {},
+                (RetryDecision::Resign, Some(signers)) => {
+                    if let Some(cache) = &self.blockhash_cache {
+                        cache.invalidate();
+                    }
+                    self.sign_transaction(transaction, signers)?;
+                },
+                _ => return Err(error),
+            }
+            thread::sleep(self.retry_policy.backoff(retry));
+            telemetry.retried(transaction);
+        }
+    }
+
+    /// Sends the transaction unless it has already landed, then waits for it to reach the commitment, rebroadcasting
+    /// it until the blockhash expires. Returns `None` right after sending when there is no commitment to wait for.
+    fn send_and_confirm_attempt(
+        &self,
+        transaction: &VersionedTransaction,
+        check_status: bool,
+        commitment: Option<CommitmentConfig>,
+    ) -> ClientResult<Option<TransactionStatus>> {
+        let signature = transaction.signatures[0];
+        let landed = check_status
+            && self
+                .get_signature_status_with_commitment(&signature, CommitmentConfig::processed())?
+                .is_some();
+        if !landed {
+            self.send_transaction(transaction)?;
+        }
+        let commitment = match commitment {
+            Some(commitment) => commitment,
+            None => return Ok(None),
+        };
+
+        let mut broadcast_at = Instant::now();
+        loop {
+            if let Some(status) = self.check_confirmation(transaction, commitment)? {
+                return Ok(Some(status));
+            }
+            if broadcast_at.elapsed() >= self.retry_policy.rebroadcast_interval {
+                self.resend_transaction(transaction)?;
+                broadcast_at = Instant::now();
+            }
+            thread::sleep(STATUS_POLL_INTERVAL);
+        }
+    }
+
+    /// Returns the status of the sent transaction once it has reached the commitment, failing when the transaction
+    /// has failed or can't land anymore.
+    pub(crate) fn check_confirmation(
+        &self,
+        transaction: &VersionedTransaction,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionStatus>> {
+        let signature = transaction.signatures[0];
+        if let Some(status) = self.get_signature_status_with_commitment(&signature, commitment)? {
+            return confirmed_status(transaction, status).map(Some);
+        }
+        if self.is_blockhash_expired(transaction)?
+            && self
+                .get_signature_status_with_commitment(&signature, CommitmentConfig::processed())?
+                .is_none()
+        {
+            return Err(ClientError::Expired(signature));
+        }
+        Ok(None)
+    }
+
+    /// Whether the blockhash of the transaction can't be used anymore, i.e. it is too old or the durable nonce has
+    /// been advanced.
+    fn is_blockhash_expired(&self, transaction: &VersionedTransaction) -> ClientResult<bool> {
+        let blockhash = transaction.message.recent_blockhash();
+        Ok(match nonce_account_of(&transaction.message) {
+            Some(nonce_account) => self.get_nonce_blockhash(&nonce_account)? != *blockhash,
+            None => !self.is_blockhash_valid(blockhash, CommitmentConfig::processed())?,
+        })
+    }
+
+    /// Simulates the transaction against the current state, collecting the changes of its writable accounts.
+    pub fn simulate(&self, transaction: &VersionedTransaction) -> ClientResult<Simulation> {
+        let accounts = Simulation::writable_accounts(transaction);
+        let accounts_before = self.get_multiple_accounts(&accounts)?;
+        let result = self.simulate_transaction(transaction, &accounts)?;
+
+        Ok(Simulation::new(transaction.clone(), result, &accounts, accounts_before))
+    }
+
+    /// Signs the instructions by the payer and the given signers, then sends them in a single transaction.
+    pub fn process_instructions(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&dyn Signer],
+    ) -> ClientResult<TransactionReceipt> {
+        self.process_instructions_with_compute_budget(instructions, signers, &self.compute_budget)
+    }
+
+    /// Same as [`Client::process_instructions`], but overrides the compute budget of the client.
+    pub fn process_instructions_with_compute_budget(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&dyn Signer],
+        compute_budget: &ComputeBudget,
+    ) -> ClientResult<TransactionReceipt> {
+        let transaction = self.prepare_transaction(instructions, signers, compute_budget, &[])?;
+        self.process_transaction_with_signers(&transaction, Some(signers), self.confirmation)
+    }
+
+    /// Same as [`Client::process_instructions`], but sends a versioned transaction compiled against the given
+    /// address lookup tables, so that it can reference more accounts than a legacy one.
+    pub fn process_instructions_with_lookup_tables(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&dyn Signer],
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> ClientResult<TransactionReceipt> {
+        let transaction = self.prepare_transaction(instructions, signers, &self.compute_budget, lookup_tables)?;
+        self.process_transaction_with_signers(&transaction, Some(signers), self.confirmation)
+    }
+
+    /// Same as [`Client::process_instructions`], but overrides the confirmation of the client.
+    pub fn process_instructions_with_confirmation(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&dyn Signer],
+        confirmation: Confirmation,
+    ) -> ClientResult<TransactionReceipt> {
+        let transaction = self.prepare_transaction(instructions, signers, &self.compute_budget, &[])?;
+        self.process_transaction_with_signers(&transaction, Some(signers), confirmation)
+    }
+
+    /// Same as [`Client::process_instructions`], but returns right after sending the transaction, leaving its
+    /// confirmation to the returned handle.
+    pub fn submit_instructions(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&dyn Signer],
+    ) -> ClientResult<PendingTransaction<'_, B>> {
+        let transaction = self.prepare_transaction(instructions, signers, &self.compute_budget, &[])?;
+        self.submit_transaction_with_signers(&transaction, Some(signers))
+    }
+
+    /// Builds a transaction of the given instructions, applying the compute budget and the durable nonce of the
+    /// client, and signs it by the payer and those of the given signers which are required.
+    ///
+    /// The transaction is a legacy one unless lookup tables are given, then a v0 message is compiled against them.
+    pub fn prepare_transaction(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&dyn Signer],
+        compute_budget: &ComputeBudget,
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> ClientResult<VersionedTransaction> {
+        let mut transaction = VersionedTransaction {
+            signatures: Vec::new(),
+            message: self.prepare_message(instructions, compute_budget, lookup_tables)?,
+        };
+        self.sign_transaction(&mut transaction, signers)?;
+        Ok(transaction)
     }
 
-    pub fn process_transaction(&self, transaction: &Transaction) -> ClientResult<()> {
-        self.send_and_confirm_transaction(transaction)?;
+    /// Compiles the message of [`Client::prepare_transaction`], leaving its blockhash unset.
+    pub(crate) fn prepare_message(
+        &self,
+        instructions: &[Instruction],
+        compute_budget: &ComputeBudget,
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> ClientResult<VersionedMessage> {
+        let unit_price = self.compute_unit_price(compute_budget, instructions)?;
+        build_message(
+            instructions,
+            &self.payer_pubkey(),
+            compute_budget,
+            unit_price,
+            self.durable_nonce.as_ref(),
+            lookup_tables,
+        )
+    }
+
+    /// Returns the blockhash to sign the message with, the one of its durable nonce or the latest one.
+    pub(crate) fn message_blockhash(&self, message: &VersionedMessage) -> ClientResult<Hash> {
+        match nonce_account_of(message) {
+            Some(nonce_account) => self.get_nonce_blockhash(&nonce_account),
+            None => self.latest_blockhash(),
+        }
+    }
+
+    /// Signs the transaction with the latest blockhash, or the blockhash of its durable nonce.
+    pub fn sign_transaction(
+        &self,
+        transaction: &mut VersionedTransaction,
+        signers: &[&dyn Signer],
+    ) -> ClientResult<()> {
+        let blockhash = self.message_blockhash(&transaction.message)?;
+        let nonce_authority = self.durable_nonce.as_ref().and_then(DurableNonceConfig::authority);
+        *transaction = sign_message(
+            &transaction.message,
+            blockhash,
+            self.payer(),
+            signers.iter().copied().chain(nonce_authority),
+        )?;
         Ok(())
     }
 
+    /// Resolves the price per compute unit for the given instructions according to the priority fee policy.
+    pub fn compute_unit_price(
+        &self,
+        compute_budget: &ComputeBudget,
+        instructions: &[Instruction],
+    ) -> ClientResult<Option<u64>> {
+        Ok(match compute_budget.priority_fee {
+            PriorityFee::None => None,
+            PriorityFee::Fixed(unit_price) => Some(unit_price),
+            PriorityFee::Percentile { percentile, max } => {
+                let fees = self.get_recent_prioritization_fees(&ComputeBudget::fee_accounts(instructions))?;
+                let unit_price = fee_percentile(fees, percentile);
+                Some(max.map_or(unit_price, |max| unit_price.min(max)))
+            },
+        })
+    }
+
+    #[instrument(skip_all, fields(owner = %owner, data_len = account_data_len))]
     pub fn create_account(
         &self,
         owner: &Pubkey,
@@ -58,41 +701,132 @@ impl Client {
             self.get_minimum_balance_for_rent_exemption(account_data_len)?
         };
 
-        let mut transaction = Transaction::new_with_payer(
-            &[system_instruction::create_account(
-                &self.payer_pubkey(),
-                &account.pubkey(),
-                lamports,
-                account_data_len as u64,
-                owner,
-            )],
-            Some(&self.payer_pubkey()),
+        let instructions = instructions::system::create_account(
+            &self.payer_pubkey(),
+            &account.pubkey(),
+            owner,
+            account_data_len,
+            lamports,
         );
-        transaction.sign(&[self.payer(), &account], self.latest_blockhash()?);
-        self.process_transaction(&transaction)?;
+        self.process_instructions(&instructions.instructions, &[&account])?;
 
         Ok(account)
     }
 
+    #[instrument(skip_all, fields(to = %to_pubkey, lamports = lamports))]
     pub fn airdrop(&self, to_pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature> {
-        let blockhash = self.client.get_latest_blockhash()?;
-        let signature = self.request_airdrop_with_blockhash(to_pubkey, lamports, &blockhash)?;
-        self.confirm_transaction_with_spinner(&signature, &blockhash, self.commitment())?;
+        self.client.airdrop(to_pubkey, lamports)
+    }
+}
+
+/// Applies the compute budget at the given unit price and the durable nonce to the instructions, then compiles them
+/// like [`compile_message`].
+pub(crate) fn build_message(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    compute_budget: &ComputeBudget,
+    unit_price: Option<u64>,
+    durable_nonce: Option<&DurableNonceConfig>,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> ClientResult<VersionedMessage> {
+    let mut instructions = compute_budget.apply(instructions, unit_price);
+    if let Some(durable_nonce) = durable_nonce {
+        instructions = durable_nonce.apply(instructions, payer);
+    }
+
+    compile_message(&instructions, payer, lookup_tables)
+}
 
-        Ok(signature)
+/// Compiles a legacy message, or a v0 one when lookup tables are given. The blockhash is set on signing.
+pub(crate) fn compile_message(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> ClientResult<VersionedMessage> {
+    Ok(if lookup_tables.is_empty() {
+        VersionedMessage::Legacy(Message::new(instructions, Some(payer)))
+    } else {
+        VersionedMessage::V0(v0::Message::try_compile(
+            payer,
+            instructions,
+            lookup_tables,
+            Hash::default(),
+        )?)
+    })
+}
+
+/// Collects the payer and the given signers required by the message, skipping the duplicates.
+pub(crate) fn transaction_signers<'a>(
+    message: &VersionedMessage,
+    payer: &'a dyn Signer,
+    signers: impl IntoIterator<Item = &'a dyn Signer>,
+) -> Vec<&'a dyn Signer> {
+    let required_signers = &message.static_account_keys()[..usize::from(message.header().num_required_signatures)];
+    let mut transaction_signers: Vec<&dyn Signer> = Vec::new();
+    for signer in iter::once(payer).chain(signers) {
+        let pubkey = signer.pubkey();
+        if required_signers.contains(&pubkey) && !transaction_signers.iter().any(|added| added.pubkey() == pubkey) {
+            transaction_signers.push(signer);
+        }
     }
+    transaction_signers
 }
 
-impl Deref for Client {
-    type Target = RpcClient;
+/// Signs the message with the blockhash by the payer and those of the given signers which it requires.
+pub(crate) fn sign_message<'a>(
+    message: &VersionedMessage,
+    blockhash: Hash,
+    payer: &'a dyn Signer,
+    signers: impl IntoIterator<Item = &'a dyn Signer>,
+) -> ClientResult<VersionedTransaction> {
+    let signers = transaction_signers(message, payer, signers);
+    let mut message = message.clone();
+    message.set_recent_blockhash(blockhash);
+    Ok(VersionedTransaction::try_new(message, &signers)?)
+}
+
+impl<B> Deref for Client<B> {
+    type Target = B;
 
     fn deref(&self) -> &Self::Target {
         &self.client
     }
 }
 
-impl DerefMut for Client {
+impl<B> DerefMut for Client<B> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.client
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::system_instruction;
+
+    use super::*;
+    use crate::mock::MockBackend;
+
+    #[test]
+    fn identical_transactions_land() {
+        let client = Client::new(MockBackend::new(), Keypair::new());
+        let transfer = system_instruction::transfer(&client.payer_pubkey(), &Pubkey::new_unique(), 100);
+
+        let first = client.process_instructions(&[transfer.clone()], &[]).unwrap();
+        let second = client.process_instructions(&[transfer], &[]).unwrap();
+
+        assert_ne!(first.signature, second.signature);
+        assert_ne!(first.slot, second.slot);
+        assert_eq!(client.transactions().len(), 2);
+    }
+
+    #[test]
+    fn blockhash_cache_reuses_blockhash() {
+        let client = Client::new(MockBackend::new(), Keypair::new()).with_blockhash_cache(Arc::default());
+        let transfer = system_instruction::transfer(&client.payer_pubkey(), &Pubkey::new_unique(), 100);
+
+        let first = client.process_instructions(&[transfer.clone()], &[]).unwrap();
+        let second = client.process_instructions(&[transfer], &[]).unwrap();
+
+        assert_eq!(first.signature, second.signature);
+    }
+}
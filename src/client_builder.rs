@@ -0,0 +1,303 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use solana_cli_config::{Config, CONFIG_FILE};
+use solana_client::{
+    http_sender::HttpSender,
+    nonblocking::rpc_client::RpcClient as AsyncRpcClient,
+    rpc_client::{RpcClient, RpcClientConfig},
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    signature::{read_keypair_file, Keypair, Signer},
+};
+
+use crate::{
+    nonblocking::AsyncClient,
+    rate_limit::{RateLimitedSender, RateLimiter},
+    Client, ClientError, ClientResult,
+};
+
+/// Timeout of RPC requests used unless set explicitly.
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Returns the URL of a cluster moniker like `devnet` or `mainnet-beta`, or the given URL as is.
+pub fn url_from_moniker(url_or_moniker: &str) -> String {
+    match url_or_moniker {
+        "m" | "mainnet-beta" => "https://api.mainnet-beta.solana.com",
+        "t" | "testnet" => "https://api.testnet.solana.com",
+        "d" | "devnet" => "https://api.devnet.solana.com",
+        "l" | "localhost" => "http://localhost:8899",
+        url => url,
+    }
+    .to_string()
+}
+
+/// Configures the connection and the payer of a [`Client`] or an [`AsyncClient`].
+///
+/// ```no_run
+/// use solana_client_helpers::ClientBuilder;
+/// use solana_sdk::commitment_config::CommitmentConfig;
+///
+/// let client = ClientBuilder::new()
+///     .url("devnet")
+///     .commitment(CommitmentConfig::confirmed())
+///     .payer_file("payer.json")
+///     .build()?;
+/// # Ok::<(), solana_client_helpers::ClientError>(())
+/// ```
+pub struct ClientBuilder {
+    url: String,
+    websocket_url: Option<String>,
+    commitment: CommitmentConfig,
+    timeout: Duration,
+    payer: Option<Box<dyn Signer + Send + Sync>>,
+    payer_path: Option<PathBuf>,
+    rate_limiter: Option<RateLimiter>,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClientBuilder {
+    pub fn new() -> Self {
+        Self {
+            url: url_from_moniker("localhost"),
+            websocket_url: None,
+            commitment: CommitmentConfig::default(),
+            timeout: DEFAULT_RPC_TIMEOUT,
+            payer: None,
+            payer_path: None,
+            rate_limiter: None,
+        }
+    }
+
+    /// Starts with the RPC and websocket URLs, the commitment and the keypair of the Solana CLI config at the path.
+    pub fn from_cli_config(path: impl AsRef<Path>) -> ClientResult<Self> {
+        let path = path.as_ref();
+        let config = Config::load(&path.to_string_lossy()).map_err(|error| ClientError::Config {
+            path: path.to_path_buf(),
+            error,
+        })?;
+
+        let mut builder = Self::new()
+            .url(&config.json_rpc_url)
+            .commitment(parse_commitment(&config.commitment)?)
+            .payer_file(config.keypair_path);
+        if !config.websocket_url.is_empty() {
+            builder = builder.websocket_url(config.websocket_url);
+        }
+        Ok(builder)
+    }
+
+    /// Same as [`ClientBuilder::from_cli_config`] for the default `~/.config/solana/cli/config.yml`.
+    pub fn from_default_cli_config() -> ClientResult<Self> {
+        let path = CONFIG_FILE.as_ref().ok_or_else(|| ClientError::Config {
+            path: PathBuf::new(),
+            error: std::io::ErrorKind::NotFound.into(),
+        })?;
+        Self::from_cli_config(path)
+    }
+
+    /// Sets the RPC URL, either a full one or a cluster moniker: `localhost`, `devnet`, `testnet`, `mainnet-beta`.
+    pub fn url(mut self, url_or_moniker: impl AsRef<str>) -> Self {
+        self.url = url_from_moniker(url_or_moniker.as_ref());
+        self
+    }
+
+    /// Sets the pubsub endpoint of the subscriptions, which is derived from the RPC URL otherwise.
+    pub fn websocket_url(mut self, websocket_url: impl Into<String>) -> Self {
+        self.websocket_url = Some(websocket_url.into());
+        self
+    }
+
+    pub fn commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// Sets the timeout of every RPC request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn payer(mut self, payer: impl Signer + Send + Sync + 'static) -> Self {
+        self.payer = Some(Box::new(payer));
+        self.payer_path = None;
+        self
+    }
+
+    /// Reads the payer keypair from the JSON file on build.
+    pub fn payer_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.payer_path = Some(path.into());
+        self.payer = None;
+        self
+    }
+
+    /// Passes every RPC request through the limiter, a clone of which keeps observing the counters of the requests.
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    pub fn build(self) -> ClientResult<Client> {
+        let config = self.rpc_client_config();
+        let client = match self.rate_limiter.clone() {
+            Some(limiter) => RpcClient::new_sender(RateLimitedSender::new(self.http_sender(), limiter), config),
+            None => RpcClient::new_sender(self.http_sender(), config),
+        };
+        let websocket_url = self.websocket_url.clone();
+        let client = Client::with_boxed_payer(client, self.take_payer()?);
+        Ok(match websocket_url {
+            Some(websocket_url) => client.with_websocket_url(websocket_url),
+            None => client,
+        })
+    }
+
+    /// Builds an [`AsyncClient`], leaving the websocket URL out since only the blocking [`Client`] subscribes.
+    pub fn build_async(self) -> ClientResult<AsyncClient> {
+        let config = self.rpc_client_config();
+        let client = match self.rate_limiter.clone() {
+            Some(limiter) => AsyncRpcClient::new_sender(RateLimitedSender::new(self.http_sender(), limiter), config),
+            None => AsyncRpcClient::new_sender(self.http_sender(), config),
+        };
+        Ok(AsyncClient::with_boxed_payer(client, self.take_payer()?))
+    }
+
+    fn http_sender(&self) -> HttpSender {
+        HttpSender::new_with_timeout(&self.url, self.timeout)
+    }
+
+    fn rpc_client_config(&self) -> RpcClientConfig {
+        RpcClientConfig::with_commitment(self.commitment)
+    }
+
+    fn take_payer(self) -> ClientResult<Box<dyn Signer + Send + Sync>> {
+        if let Some(payer) = self.payer {
+            return Ok(payer);
+        }
+        let path = self.payer_path.ok_or(ClientError::MissingPayer)?;
+        let payer: Keypair = read_keypair_file(&path).map_err(|error| ClientError::Keypair {
+            path,
+            message: error.to_string(),
+        })?;
+        Ok(Box::new(payer))
+    }
+}
+
+fn parse_commitment(commitment: &str) -> ClientResult<CommitmentConfig> {
+    CommitmentConfig::from_str(commitment).map_err(|_| ClientError::InvalidCommitment(commitment.to_string()))
+}
+
+impl Client {
+    /// Connects to the cluster of the Solana CLI config at the path, paying with its keypair.
+    pub fn from_cli_config(path: impl AsRef<Path>) -> ClientResult<Self> {
+        ClientBuilder::from_cli_config(path)?.build()
+    }
+}
+
+impl AsyncClient {
+    /// Connects to the cluster of the Solana CLI config at the path, paying with its keypair.
+    pub fn from_cli_config(path: impl AsRef<Path>) -> ClientResult<Self> {
+        ClientBuilder::from_cli_config(path)?.build_async()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use solana_sdk::signature::write_keypair_file;
+
+    use super::*;
+
+    /// Creates an empty directory of its own for the files of a test.
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("solana-client-helpers-{}", Keypair::new().pubkey()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn monikers_are_expanded() {
+        for (short, long, url) in [
+            ("m", "mainnet-beta", "https://api.mainnet-beta.solana.com"),
+            ("t", "testnet", "https://api.testnet.solana.com"),
+            ("d", "devnet", "https://api.devnet.solana.com"),
+            ("l", "localhost", "http://localhost:8899"),
+        ] {
+            assert_eq!(url_from_moniker(short), url);
+            assert_eq!(url_from_moniker(long), url);
+        }
+        assert_eq!(url_from_moniker("https://rpc.example.com"), "https://rpc.example.com");
+    }
+
+    #[test]
+    fn commitment_is_parsed() {
+        assert_eq!(parse_commitment("finalized").unwrap(), CommitmentConfig::finalized());
+        assert!(matches!(
+            parse_commitment("eventually"),
+            Err(ClientError::InvalidCommitment(commitment)) if commitment == "eventually"
+        ));
+    }
+
+    #[test]
+    fn cli_config_is_loaded() {
+        let dir = temp_dir();
+        let payer = Keypair::new();
+        let keypair_path = dir.join("payer.json");
+        write_keypair_file(&payer, &keypair_path).unwrap();
+        let config_path = dir.join("config.yml");
+        let config = Config {
+            json_rpc_url: "https://rpc.example.com".to_string(),
+            websocket_url: "wss://rpc.example.com/ws".to_string(),
+            keypair_path: keypair_path.to_string_lossy().into_owned(),
+            commitment: "finalized".to_string(),
+            ..Config::default()
+        };
+        config.save(&config_path.to_string_lossy()).unwrap();
+
+        let client = Client::from_cli_config(&config_path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(client.url(), "https://rpc.example.com");
+        assert_eq!(client.websocket_url.as_deref(), Some("wss://rpc.example.com/ws"));
+        assert_eq!(client.commitment(), CommitmentConfig::finalized());
+        assert_eq!(client.payer_pubkey(), payer.pubkey());
+    }
+
+    #[test]
+    fn missing_payer_fails() {
+        assert!(matches!(ClientBuilder::new().build(), Err(ClientError::MissingPayer)));
+    }
+
+    #[test]
+    fn unreadable_keypair_fails() {
+        let dir = temp_dir();
+        let keypair_path = dir.join("payer.json");
+        fs::write(&keypair_path, "not a keypair").unwrap();
+
+        let result = ClientBuilder::new().payer_file(&keypair_path).build();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(result, Err(ClientError::Keypair { path, .. }) if path == keypair_path));
+    }
+
+    #[test]
+    fn missing_config_fails() {
+        let dir = temp_dir();
+        let config_path = dir.join("config.yml");
+
+        let result = ClientBuilder::from_cli_config(&config_path);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(result, Err(ClientError::Config { path, .. }) if path == config_path));
+    }
+}
@@ -0,0 +1,171 @@
+use solana_sdk::{compute_budget::ComputeBudgetInstruction, instruction::Instruction, pubkey::Pubkey};
+
+/// Price paid for every requested compute unit, in micro-lamports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PriorityFee {
+    /// No price instruction is added to transactions.
+    #[default]
+    None,
+
+    /// Fixed price in micro-lamports per compute unit.
+    Fixed(u64),
+
+    /// Price taken from the given percentile of the fees recently paid for the writable accounts of a transaction
+    /// (see `getRecentPrioritizationFees`), optionally capped by `max`.
+    Percentile { percentile: u8, max: Option<u64> },
+}
+
+/// Compute budget requested for every transaction sent by the client.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ComputeBudget {
+    pub unit_limit: Option<u32>,
+    pub priority_fee: PriorityFee,
+}
+
+impl ComputeBudget {
+    pub fn with_unit_limit(mut self, unit_limit: u32) -> Self {
+        self.unit_limit = Some(unit_limit);
+        self
+    }
+
+    pub fn with_priority_fee(mut self, priority_fee: PriorityFee) -> Self {
+        self.priority_fee = priority_fee;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.unit_limit.is_none() && self.priority_fee == PriorityFee::None
+    }
+
+    /// Accounts whose recent prioritization fees determine the price of a transaction.
+    pub fn fee_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+        let mut accounts = Vec::new();
+        for account in instructions.iter().flat_map(|instruction| &instruction.accounts) {
+            if account.is_writable && !accounts.contains(&account.pubkey) {
+                accounts.push(account.pubkey);
+            }
+        }
+        accounts
+    }
+
+    /// Prepends the compute budget instructions to the given ones, unless they already request a compute budget.
+    pub fn apply(&self, instructions: &[Instruction], unit_price: Option<u64>) -> Vec<Instruction> {
+        let requested = instructions
+            .iter()
+            .any(|instruction| solana_sdk::compute_budget::check_id(&instruction.program_id));
+
+        let mut result = Vec::with_capacity(instructions.len() + 2);
+        if !requested {
+            if let Some(unit_limit) = self.unit_limit {
+                result.push(ComputeBudgetInstruction::set_compute_unit_limit(unit_limit));
+            }
+            if let Some(unit_price) = unit_price {
+                result.push(ComputeBudgetInstruction::set_compute_unit_price(unit_price));
+            }
+        }
+        result.extend_from_slice(instructions);
+        result
+    }
+
+    /// Instructions with the largest possible price, used to reserve space in size-bounded transactions.
+    pub fn apply_placeholder(&self, instructions: &[Instruction]) -> Vec<Instruction> {
+        let unit_price = match self.priority_fee {
+            PriorityFee::None => None,
+            PriorityFee::Fixed(_) | PriorityFee::Percentile { .. } => Some(u64::MAX),
+        };
+        self.apply(instructions, unit_price)
+    }
+}
+
+/// Returns the fee at the given percentile of the recently paid prioritization fees.
+pub fn fee_percentile(mut fees: Vec<u64>, percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+
+    fees.sort_unstable();
+    let index = (fees.len() - 1) * usize::from(percentile.min(100)) / 100;
+    fees[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{instruction::AccountMeta, system_instruction};
+
+    use super::*;
+
+    fn transfer() -> Instruction {
+        system_instruction::transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1)
+    }
+
+    #[test]
+    fn fee_percentile_picks_sorted_fee() {
+        let fees = vec![50, 10, 30, 20, 40];
+
+        assert_eq!(fee_percentile(Vec::new(), 50), 0);
+        assert_eq!(fee_percentile(fees.clone(), 0), 10);
+        assert_eq!(fee_percentile(fees.clone(), 50), 30);
+        assert_eq!(fee_percentile(fees.clone(), 75), 40);
+        assert_eq!(fee_percentile(fees.clone(), 100), 50);
+        assert_eq!(fee_percentile(fees, 200), 50);
+    }
+
+    #[test]
+    fn apply_prepends_requested_budget() {
+        let instructions = [transfer()];
+        let compute_budget = ComputeBudget::default()
+            .with_unit_limit(200_000)
+            .with_priority_fee(PriorityFee::Fixed(10));
+
+        assert_eq!(compute_budget.apply(&instructions, Some(10)), [
+            ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+            ComputeBudgetInstruction::set_compute_unit_price(10),
+            instructions[0].clone(),
+        ]);
+        assert_eq!(compute_budget.apply(&instructions, None), [
+            ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+            instructions[0].clone(),
+        ]);
+        assert_eq!(ComputeBudget::default().apply(&instructions, None), instructions);
+    }
+
+    #[test]
+    fn apply_keeps_budget_of_instructions() {
+        let instructions = [ComputeBudgetInstruction::set_compute_unit_limit(5_000), transfer()];
+        let compute_budget = ComputeBudget::default().with_unit_limit(200_000);
+
+        assert_eq!(compute_budget.apply(&instructions, Some(10)), instructions);
+    }
+
+    #[test]
+    fn placeholder_reserves_price_of_priority_fee() {
+        let instructions = [transfer()];
+        let percentile = PriorityFee::Percentile {
+            percentile: 75,
+            max: Some(1_000),
+        };
+
+        let placeholder = ComputeBudgetInstruction::set_compute_unit_price(u64::MAX);
+
+        assert_eq!(ComputeBudget::default().apply_placeholder(&instructions), instructions);
+        assert_eq!(
+            ComputeBudget::default()
+                .with_priority_fee(percentile)
+                .apply_placeholder(&instructions),
+            [placeholder, instructions[0].clone()]
+        );
+    }
+
+    #[test]
+    fn fee_accounts_are_writable_accounts() {
+        let (writable, readonly) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![
+            AccountMeta::new(writable, true),
+            AccountMeta::new_readonly(readonly, false),
+        ]);
+
+        let instructions = [instruction.clone(), instruction];
+
+        assert_eq!(ComputeBudget::fee_accounts(&instructions), [writable]);
+    }
+}
@@ -0,0 +1,271 @@
+//! Confirmation levels of the sent transactions, and handles of the transactions sent now and confirmed later.
+//!
+//! Jobs sending many independent transactions can pipeline them, submitting all of them first and confirming them
+//! afterwards:
+//!
+//! ```no_run
+//! use solana_client_helpers::{instructions, Client};
+//! use solana_sdk::signature::Signer;
+//! # use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+//! # let (client, owner, token_mint, source): (Client, Keypair, Pubkey, Pubkey) = unimplemented!();
+//! # let recipients: Vec<Pubkey> = vec![];
+//!
+//! let mut pending = Vec::new();
+//! for recipient in &recipients {
+//!     let transfer = instructions::token::transfer_to(&owner.pubkey(), &[], &token_mint, &source, recipient, 100, 2)?;
+//!     pending.push(client.submit_instructions(&transfer.instructions, &[&owner])?);
+//! }
+//! for transaction in pending {
+//!     let receipt = transaction.confirm()?;
+//!     println!("{} in slot {:?}", receipt.signature, receipt.slot);
+//! }
+//! # Ok::<(), solana_client_helpers::ClientError>(())
+//! ```
+
+use std::{thread, time::Instant};
+
+use solana_sdk::{commitment_config::CommitmentConfig, signature::Signature, transaction::VersionedTransaction};
+use tracing::Span;
+
+use crate::{
+    backend::Backend,
+    error::decode_send_error,
+    receipt::{TransactionReceipt, TransactionStatus},
+    retry::{is_transient, STATUS_POLL_INTERVAL},
+    telemetry::TransactionTelemetry,
+    Client, ClientResult, RpcClient,
+};
+
+/// How long the client waits for a transaction after sending it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Confirmation {
+    /// Returns as soon as the transaction is sent, without waiting for it to land.
+    ///
+    /// Helpers sending several transactions depending on each other may fail, as the next transaction can be sent
+    /// before the previous one lands.
+    Sent,
+
+    /// Waits for the commitment of the backend.
+    Default,
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl Default for Confirmation {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+impl Confirmation {
+    /// Returns the commitment to wait for, `None` when the transaction isn't waited for.
+    pub fn commitment(self, default: CommitmentConfig) -> Option<CommitmentConfig> {
+        match self {
+            Self::Sent => None,
+            Self::Default => Some(default),
+            Self::Processed => Some(CommitmentConfig::processed()),
+            Self::Confirmed => Some(CommitmentConfig::confirmed()),
+            Self::Finalized => Some(CommitmentConfig::finalized()),
+        }
+    }
+}
+
+/// Evaluates the signature status of a sent transaction at the awaited commitment, decoding the error of a failed one.
+pub(crate) fn confirmed_status(
+    transaction: &VersionedTransaction,
+    status: TransactionStatus,
+) -> ClientResult<TransactionStatus> {
+    match status.err.clone() {
+        Some(error) => Err(decode_send_error(error.into(), transaction)),
+        None => Ok(status),
+    }
+}
+
+/// Transaction sent by [`Client::submit_transaction`], which can be polled or waited for until it is confirmed.
+///
+/// The transaction is rebroadcast while it is polled, but never re-signed, so it fails with
+/// [`ClientError::Expired`](crate::ClientError::Expired) once its blockhash expires.
+pub struct PendingTransaction<'a, B = RpcClient> {
+    client: &'a Client<B>,
+    transaction: VersionedTransaction,
+    commitment: CommitmentConfig,
+    broadcast_at: Instant,
+    telemetry: Option<TransactionTelemetry>,
+    receipt: Option<TransactionReceipt>,
+}
+
+impl<'a, B: Backend> PendingTransaction<'a, B> {
+    pub(crate) fn new(
+        client: &'a Client<B>,
+        transaction: VersionedTransaction,
+        commitment: CommitmentConfig,
+        telemetry: TransactionTelemetry,
+    ) -> Self {
+        Self {
+            client,
+            transaction,
+            commitment,
+            broadcast_at: Instant::now(),
+            telemetry: Some(telemetry),
+            receipt: None,
+        }
+    }
+
+    /// Creates a handle of a transaction which doesn't have to be waited for, e.g. a simulated one.
+    pub(crate) fn resolved(
+        client: &'a Client<B>,
+        transaction: VersionedTransaction,
+        receipt: TransactionReceipt,
+    ) -> Self {
+        Self {
+            client,
+            transaction,
+            commitment: client.commitment(),
+            broadcast_at: Instant::now(),
+            telemetry: None,
+            receipt: Some(receipt),
+        }
+    }
+
+    /// Sets the commitment the transaction is waited for, the one of the backend by default.
+    pub fn with_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    pub fn signature(&self) -> Signature {
+        self.transaction.signatures[0]
+    }
+
+    pub fn transaction(&self) -> &VersionedTransaction {
+        &self.transaction
+    }
+
+    /// Checks the status of the transaction once, rebroadcasting it when it is due. Returns the receipt once the
+    /// transaction is confirmed, `None` until then.
+    pub fn poll(&mut self) -> ClientResult<Option<TransactionReceipt>> {
+        if let Some(receipt) = &self.receipt {
+            return Ok(Some(receipt.clone()));
+        }
+
+        let span = self
+            .telemetry
+            .as_ref()
+            .map_or_else(Span::none, |telemetry| telemetry.span().clone());
+        let _entered = span.enter();
+
+        match self.client.check_confirmation(&self.transaction, self.commitment) {
+            Ok(Some(status)) => {
+                let receipt = self.client.receipt(&self.transaction, Some(&status));
+                self.finish(Ok(receipt))
+            },
+            Ok(None) => {
+                if self.broadcast_at.elapsed() >= self.client.retry_policy.rebroadcast_interval {
+                    self.client.resend_transaction(&self.transaction)?;
+                    self.broadcast_at = Instant::now();
+                }
+                Ok(None)
+            },
+            Err(error) if is_transient(&error) => Err(error),
+            Err(error) => self.finish(Err(error)),
+        }
+    }
+
+    /// Waits until the transaction is confirmed.
+    pub fn confirm(mut self) -> ClientResult<TransactionReceipt> {
+        loop {
+            if let Some(receipt) = self.poll()? {
+                return Ok(receipt);
+            }
+            thread::sleep(STATUS_POLL_INTERVAL);
+        }
+    }
+
+    fn finish(&mut self, result: ClientResult<TransactionReceipt>) -> ClientResult<Option<TransactionReceipt>> {
+        if let Some(telemetry) = self.telemetry.take() {
+            let fee = result.as_ref().ok().and_then(|receipt| receipt.fee);
+            telemetry.finish(&result, fee);
+        }
+
+        let receipt = self.client.record_receipt(result?);
+        self.receipt = Some(receipt.clone());
+        Ok(Some(receipt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    };
+    use spl_token::error::TokenError;
+
+    use super::*;
+    use crate::{error::SplError, mock::MockBackend, ClientError};
+
+    fn token_transfer(client: &Client<MockBackend>) -> Instruction {
+        let (source, destination) = (Pubkey::new_unique(), Pubkey::new_unique());
+        spl_token::instruction::transfer(&spl_token::id(), &source, &destination, &client.payer_pubkey(), &[], 40)
+            .unwrap()
+    }
+
+    #[test]
+    fn sent_transaction_is_not_waited_for() {
+        let client = Client::new(MockBackend::new(), Keypair::new());
+        client.delay_transaction(0, usize::MAX);
+
+        let receipt = client
+            .process_instructions_with_confirmation(&[token_transfer(&client)], &[], Confirmation::Sent)
+            .unwrap();
+
+        assert_eq!(receipt.signature, client.transactions()[0].signatures[0]);
+        assert_eq!(receipt.slot, None);
+        assert_eq!(receipt.confirmation_status, None);
+    }
+
+    #[test]
+    fn submitted_transaction_lands_when_polled() {
+        let client = Client::new(MockBackend::new(), Keypair::new());
+        client.delay_transaction(0, 1);
+
+        let mut pending = client.submit_instructions(&[token_transfer(&client)], &[]).unwrap();
+        assert!(pending.poll().unwrap().is_none());
+
+        let receipt = pending.poll().unwrap().unwrap();
+        assert_eq!(receipt.signature, pending.signature());
+        assert_eq!(receipt.slot, Some(0));
+        assert!(receipt.confirmation_status.is_some());
+    }
+
+    #[test]
+    fn dropped_transaction_expires() {
+        let client = Client::new(MockBackend::new(), Keypair::new());
+        client.drop_transaction(0);
+
+        let pending = client.submit_instructions(&[token_transfer(&client)], &[]).unwrap();
+        let signature = pending.signature();
+
+        assert!(matches!(pending.confirm(), Err(ClientError::Expired(expired)) if expired == signature));
+    }
+
+    #[test]
+    fn failed_transaction_is_decoded() {
+        let client = Client::new(MockBackend::new(), Keypair::new());
+        client.fail_transaction(0, TransactionError::InstructionError(0, InstructionError::Custom(1)));
+
+        let pending = client.submit_instructions(&[token_transfer(&client)], &[]).unwrap();
+
+        assert!(matches!(
+            pending.confirm(),
+            Err(ClientError::Instruction {
+                index: 0,
+                error: SplError::Token(TokenError::InsufficientFunds),
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::{PoisonError, RwLock};
+
+use num_traits::FromPrimitive;
+use solana_client::{
+    client_error::{self, ClientErrorKind},
+    rpc_request::{RpcError, RpcResponseErrorData},
+};
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    transaction::{TransactionError, VersionedTransaction},
+};
+use spl_token::error::TokenError;
+use spl_token_swap::error::SwapError;
+use thiserror::Error;
+
+use crate::ClientError;
+
+/// Deployments of the token swap program registered besides the SPL one.
+static SWAP_PROGRAMS: RwLock<Vec<Pubkey>> = RwLock::new(Vec::new());
+
+/// Registers a deployment of the token swap program, so that its custom errors are decoded as [`SwapError`]s.
+pub fn register_swap_program(program_id: Pubkey) {
+    let mut programs = SWAP_PROGRAMS.write().unwrap_or_else(PoisonError::into_inner);
+    if !programs.contains(&program_id) {
+        programs.push(program_id);
+    }
+}
+
+/// Whether the program is the SPL token swap program or a deployment of it registered with
+/// [`register_swap_program`].
+pub fn is_swap_program(program_id: &Pubkey) -> bool {
+    spl_token_swap::check_id(program_id)
+        || SWAP_PROGRAMS
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(program_id)
+}
+
+/// Custom error of a program the helpers interact with.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SplError {
+    #[error(transparent)]
+    Token(TokenError),
+
+    #[error(transparent)]
+    Swap(SwapError),
+}
+
+impl SplError {
+    /// Decodes the custom error code returned by the program of a failed instruction.
+    ///
+    /// Token swap programs are deployed under many addresses, so the errors of a deployment other than the SPL one are
+    /// only decoded once it is registered with [`register_swap_program`].
+    pub fn decode(program_id: &Pubkey, code: u32) -> Option<Self> {
+        if spl_token::check_id(program_id) {
+            TokenError::from_u32(code).map(Self::Token)
+        } else if is_swap_program(program_id) {
+            SwapError::from_u32(code).map(Self::Swap)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the index of the failed instruction and its decoded error, if the error is a custom one.
+    pub fn from_transaction_error(transaction: &VersionedTransaction, error: &TransactionError) -> Option<(u8, Self)> {
+        match error {
+            TransactionError::InstructionError(index, InstructionError::Custom(code)) => {
+                let message = &transaction.message;
+                let instruction = message.instructions().get(usize::from(*index))?;
+                // Programs can't be loaded from lookup tables, so their ids are always among the static keys.
+                let program_id = instruction.program_id(message.static_account_keys());
+                Self::decode(program_id, *code).map(|error| (*index, error))
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Extracts the transaction error and the program logs from a failed send.
+fn transaction_failure(error: &client_error::ClientError) -> Option<(TransactionError, Vec<String>)> {
+    match error.kind() {
+        ClientErrorKind::TransactionError(error) => Some((error.clone(), Vec::new())),
+        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+            ..
+        }) => result
+            .err
+            .clone()
+            .map(|error| (error, result.logs.clone().unwrap_or_default())),
+        _ => None,
+    }
+}
+
+/// Converts the error of sending the transaction, decoding the custom errors of the known programs.
+pub(crate) fn decode_send_error(error: client_error::ClientError, transaction: &VersionedTransaction) -> ClientError {
+    if let Some((transaction_error, logs)) = transaction_failure(&error) {
+        if let Some((index, error)) = SplError::from_transaction_error(transaction, &transaction_error) {
+            return ClientError::Instruction { index, error, logs };
+        }
+    }
+    error.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_errors_are_decoded_for_registered_programs() {
+        let code = SwapError::InvalidInput as u32;
+        assert!(matches!(
+            SplError::decode(&spl_token_swap::id(), code),
+            Some(SplError::Swap(SwapError::InvalidInput))
+        ));
+
+        let program_id = Pubkey::new_unique();
+        assert!(SplError::decode(&program_id, code).is_none());
+
+        register_swap_program(program_id);
+        assert!(matches!(
+            SplError::decode(&program_id, code),
+            Some(SplError::Swap(SwapError::InvalidInput))
+        ));
+        assert!(matches!(
+            SplError::decode(&spl_token::id(), TokenError::InsufficientFunds as u32),
+            Some(SplError::Token(TokenError::InsufficientFunds))
+        ));
+    }
+}
@@ -0,0 +1,882 @@
+//! Backend spreading the calls of the helpers over several RPC endpoints and failing over between them.
+//!
+//! Reads go to the healthy endpoints in turn and move on to the next one when an endpoint fails to answer. The
+//! validity of a blockhash and the status of a transaction are asked of all the healthy endpoints at once, taking the
+//! most advanced answer, so that an endpoint lagging behind doesn't expire a transaction early or make it be
+//! rebroadcast. Transactions are sent to the first healthy endpoint or to all of them, depending on the [`SendMode`].
+//! An endpoint is unhealthy while it lags behind the others by too many slots or fails too many of the recent calls,
+//! and it is only called when no healthy one is left. The slots of all the endpoints are probed at once, every
+//! [`HealthConfig::check_interval`].
+//!
+//! Each endpoint is a backend of its own, so they can be RPC clients of different providers, or of local HTTP
+//! stand-ins in tests:
+//!
+//! ```
+//! use solana_client_helpers::{failover::FailoverBackend, mock::MockBackend, Backend};
+//! use solana_sdk::{account::Account, pubkey::Pubkey, system_program};
+//!
+//! let pubkey = Pubkey::new_unique();
+//! let degraded = MockBackend::new();
+//! degraded.fail_account(pubkey, "connection refused");
+//! let healthy = MockBackend::new().with_account(pubkey, Account::new(42, 0, &system_program::id()));
+//!
+//! let backend = FailoverBackend::new(vec![degraded, healthy]).unwrap();
+//! let account = backend.get_account_with_commitment(&pubkey, backend.commitment()).unwrap();
+//! assert_eq!(account.map(|account| account.lamports), Some(42));
+//! ```
+
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, MutexGuard, PoisonError,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient as AsyncRpcClient, rpc_client::RpcClient,
+    rpc_response::RpcSimulateTransactionResult,
+};
+use solana_sdk::{
+    account::Account, clock::Slot, commitment_config::CommitmentConfig, hash::Hash, message::VersionedMessage,
+    pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction,
+};
+
+use crate::{
+    backend::Backend,
+    nonblocking::backend::AsyncBackend,
+    receipt::{TransactionConfirmationStatus, TransactionDetails, TransactionStatus},
+    ClientError, ClientResult,
+};
+
+/// Future returned by the calls of an [`AsyncBackend`].
+type BackendFuture<'a, T> = Pin<Box<dyn Future<Output = ClientResult<T>> + Send + 'a>>;
+
+/// Endpoints transactions are sent to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SendMode {
+    /// The first healthy endpoint in the order they were given, failing over to the next ones.
+    #[default]
+    Primary,
+
+    /// Every healthy endpoint, so that a transaction reaches the leader even when some of them drop it.
+    All,
+}
+
+/// Thresholds an endpoint is considered unhealthy beyond.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthConfig {
+    /// Number of slots an endpoint may lag behind the most advanced one.
+    pub max_slot_lag: u64,
+
+    /// Share of failed calls among the recent ones of an endpoint.
+    pub max_error_rate: f64,
+
+    /// Number of the recent calls the error rate is measured over.
+    pub error_window: usize,
+
+    /// Interval of probing the slots of the endpoints.
+    pub check_interval: Duration,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            max_slot_lag: 50,
+            max_error_rate: 0.5,
+            error_window: 20,
+            check_interval: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct EndpointHealth {
+    /// Slot of the last successful probe.
+    slot: Option<Slot>,
+
+    /// Outcomes of the recent calls, `true` for the failed ones.
+    failures: VecDeque<bool>,
+}
+
+impl EndpointHealth {
+    fn error_rate(&self) -> f64 {
+        if self.failures.is_empty() {
+            return 0.0;
+        }
+        self.failures.iter().filter(|failed| **failed).count() as f64 / self.failures.len() as f64
+    }
+}
+
+/// Backend on top of several endpoints, see the [module docs](self).
+#[derive(Debug)]
+pub struct FailoverBackend<B = RpcClient> {
+    endpoints: Vec<B>,
+    send_mode: SendMode,
+    health_config: HealthConfig,
+    health: Mutex<Vec<EndpointHealth>>,
+    checked_at: Mutex<Option<Instant>>,
+    next_read: AtomicUsize,
+}
+
+impl FailoverBackend<RpcClient> {
+    pub fn from_urls(
+        urls: impl IntoIterator<Item = impl ToString>,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Self> {
+        Self::new(
+            urls.into_iter()
+                .map(|url| RpcClient::new_with_commitment(url.to_string(), commitment)),
+        )
+    }
+}
+
+impl FailoverBackend<AsyncRpcClient> {
+    pub fn from_urls(
+        urls: impl IntoIterator<Item = impl ToString>,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Self> {
+        Self::new(
+            urls.into_iter()
+                .map(|url| AsyncRpcClient::new_with_commitment(url.to_string(), commitment)),
+        )
+    }
+}
+
+impl<B> FailoverBackend<B> {
+    /// Creates the backend of the endpoints in the order of their priority, failing if none is given.
+    pub fn new(endpoints: impl IntoIterator<Item = B>) -> ClientResult<Self> {
+        let endpoints: Vec<B> = endpoints.into_iter().collect();
+        if endpoints.is_empty() {
+            return Err(ClientError::MissingEndpoints);
+        }
+
+        Ok(Self {
+            health: Mutex::new(endpoints.iter().map(|_| EndpointHealth::default()).collect()),
+            endpoints,
+            send_mode: SendMode::default(),
+            health_config: HealthConfig::default(),
+            checked_at: Mutex::default(),
+            next_read: AtomicUsize::default(),
+        })
+    }
+
+    pub fn with_send_mode(mut self, send_mode: SendMode) -> Self {
+        self.send_mode = send_mode;
+        self
+    }
+
+    pub fn with_health_config(mut self, health_config: HealthConfig) -> Self {
+        self.health_config = health_config;
+        self
+    }
+
+    pub fn endpoints(&self) -> &[B] {
+        &self.endpoints
+    }
+
+    /// Returns whether the endpoint with the given index passes the health thresholds.
+    pub fn is_healthy(&self, index: usize) -> bool {
+        self.healthy_endpoints().contains(&index)
+    }
+
+    /// Returns the indices of the endpoints passing the health thresholds.
+    pub fn healthy_endpoints(&self) -> Vec<usize> {
+        let health = self.health();
+        let max_slot = health.iter().filter_map(|endpoint| endpoint.slot).max();
+
+        (0..health.len())
+            .filter(|index| {
+                let endpoint = &health[*index];
+                let lag = match (max_slot, endpoint.slot) {
+                    (Some(max_slot), Some(slot)) => max_slot - slot,
+                    (Some(_), None) => u64::MAX,
+                    (None, _) => 0,
+                };
+                lag <= self.health_config.max_slot_lag && endpoint.error_rate() <= self.health_config.max_error_rate
+            })
+            .collect()
+    }
+
+    fn health(&self) -> MutexGuard<'_, Vec<EndpointHealth>> {
+        self.health.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns whether the slots should be probed, taking the probe over when so.
+    fn take_health_check(&self) -> bool {
+        let mut checked_at = self.checked_at.lock().unwrap_or_else(PoisonError::into_inner);
+        match *checked_at {
+            Some(at) if at.elapsed() < self.health_config.check_interval => false,
+            _ => {
+                *checked_at = Some(Instant::now());
+                true
+            },
+        }
+    }
+
+    /// Counts the outcome of a call to the endpoint into its error rate, passing the result through.
+    fn record<T>(&self, index: usize, result: ClientResult<T>) -> ClientResult<T> {
+        let failed = matches!(&result, Err(error) if is_endpoint_error(error));
+
+        let mut health = self.health();
+        let failures = &mut health[index].failures;
+        failures.push_back(failed);
+        while failures.len() > self.health_config.error_window {
+            failures.pop_front();
+        }
+        result
+    }
+
+    fn record_slot(&self, index: usize, result: ClientResult<Slot>) {
+        if let Ok(slot) = self.record(index, result) {
+            self.health()[index].slot = Some(slot);
+        }
+    }
+
+    /// Healthy endpoints followed by the unhealthy ones, which are only called as the last resort.
+    fn with_unhealthy(&self, mut order: Vec<usize>) -> Vec<usize> {
+        order.extend((0..self.endpoints.len()).filter(|index| !order.contains(index)));
+        order
+    }
+
+    /// Order of the endpoints for a read, starting from the next healthy one in turn.
+    fn read_order(&self) -> Vec<usize> {
+        let mut healthy = self.healthy_endpoints();
+        if !healthy.is_empty() {
+            let start = self.next_read.fetch_add(1, Ordering::Relaxed) % healthy.len();
+            healthy.rotate_left(start);
+        }
+        self.with_unhealthy(healthy)
+    }
+
+    /// Order of the endpoints for a call which should only be made once, following their priority.
+    fn primary_order(&self) -> Vec<usize> {
+        self.with_unhealthy(self.healthy_endpoints())
+    }
+
+    /// Healthy endpoints called at once, and the unhealthy ones only tried if none of them answers.
+    fn all_healthy_order(&self) -> (Vec<usize>, Vec<usize>) {
+        let healthy = self.healthy_endpoints();
+        let unhealthy = self.with_unhealthy(healthy.clone()).split_off(healthy.len());
+        (healthy, unhealthy)
+    }
+
+    /// Endpoints a transaction is sent to at once, the other ones are only tried if all of them fail.
+    fn send_order(&self) -> (Vec<usize>, Vec<usize>) {
+        let order = self.primary_order();
+        match self.send_mode {
+            SendMode::Primary => (Vec::new(), order),
+            SendMode::All => {
+                let healthy = self.healthy_endpoints().len().max(1);
+                (order[..healthy].to_vec(), order[healthy..].to_vec())
+            },
+        }
+    }
+}
+
+/// The endpoint failed to answer, unlike a call rejected for the request itself, which fails on any endpoint.
+fn is_endpoint_error(error: &ClientError) -> bool {
+    match error {
+        ClientError::Client(error) => error.get_transaction_error().is_none(),
+        _ => false,
+    }
+}
+
+/// Result of the same call to several endpoints: their answers merged, or the most meaningful error if none
+/// answered.
+fn merge_results<T>(results: Vec<ClientResult<T>>, merge: impl Fn(T, T) -> T) -> Option<ClientResult<T>> {
+    let mut merged = None;
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(value) => {
+                merged = Some(match merged.take() {
+                    Some(merged) => merge(merged, value),
+                    None => value,
+                })
+            },
+            Err(error) => errors.push(error),
+        }
+    }
+    if let Some(value) = merged {
+        return Some(Ok(value));
+    }
+
+    let index = errors.iter().position(|error| !is_endpoint_error(error));
+    match index {
+        Some(index) => Some(Err(errors.swap_remove(index))),
+        None => errors.pop().map(Err),
+    }
+}
+
+/// The more advanced of the statuses of a transaction reported by two endpoints.
+fn latest_status(first: Option<TransactionStatus>, second: Option<TransactionStatus>) -> Option<TransactionStatus> {
+    let rank = |status: &Option<TransactionStatus>| {
+        status.as_ref().map(|status| {
+            let confirmation = match status.confirmation_status() {
+                TransactionConfirmationStatus::Processed => 0,
+                TransactionConfirmationStatus::Confirmed => 1,
+                TransactionConfirmationStatus::Finalized => 2,
+            };
+            (confirmation, status.slot)
+        })
+    };
+    if rank(&second) > rank(&first) {
+        second
+    } else {
+        first
+    }
+}
+
+impl<B: Backend + Sync> FailoverBackend<B> {
+    /// Probes the slots of all the endpoints at once.
+    pub fn check_health(&self) {
+        let all = (0..self.endpoints.len()).collect::<Vec<_>>();
+        let slots = self.call_each(&all, |endpoint| {
+            endpoint.get_slot_with_commitment(endpoint.commitment())
+        });
+        for (index, slot) in all.into_iter().zip(slots) {
+            self.record_slot(index, slot);
+        }
+    }
+
+    fn check_health_if_due(&self) {
+        if self.take_health_check() {
+            self.check_health();
+        }
+    }
+
+    /// Makes the call to the given endpoints at once, each on a thread of its own.
+    fn call_each<T: Send>(
+        &self,
+        indices: &[usize],
+        call: impl Fn(&B) -> ClientResult<T> + Sync,
+    ) -> Vec<ClientResult<T>> {
+        if let [index] = indices {
+            return vec![call(&self.endpoints[*index])];
+        }
+
+        let call = &call;
+        thread::scope(|scope| {
+            let calls = indices
+                .iter()
+                .map(|index| scope.spawn(move || call(&self.endpoints[*index])))
+                .collect::<Vec<_>>();
+            calls
+                .into_iter()
+                .map(|call| call.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                .collect()
+        })
+    }
+
+    /// Makes the call to the endpoints in the given order until one of them answers.
+    fn call<T>(&self, order: Vec<usize>, call: impl Fn(&B) -> ClientResult<T>) -> ClientResult<T> {
+        let mut last_error = None;
+        for index in order {
+            match self.record(index, call(&self.endpoints[index])) {
+                Err(error) if is_endpoint_error(&error) => last_error = Some(error),
+                result => return result,
+            }
+        }
+        Err(last_error.expect("at least one endpoint is called"))
+    }
+
+    /// Makes the call to all the endpoints of `at_once`, merging their answers, and to the `fallback` ones in turn
+    /// only if none of them answers.
+    fn fan_out<T: Send>(
+        &self,
+        (at_once, fallback): (Vec<usize>, Vec<usize>),
+        call: impl Fn(&B) -> ClientResult<T> + Sync,
+        merge: impl Fn(T, T) -> T,
+    ) -> ClientResult<T> {
+        let results = at_once
+            .iter()
+            .zip(self.call_each(&at_once, &call))
+            .map(|(index, result)| self.record(*index, result))
+            .collect();
+
+        match merge_results(results, merge) {
+            Some(Err(error)) if is_endpoint_error(&error) && !fallback.is_empty() => self.call(fallback, call),
+            Some(result) => result,
+            None => self.call(fallback, call),
+        }
+    }
+
+    fn read<T>(&self, call: impl Fn(&B) -> ClientResult<T>) -> ClientResult<T> {
+        self.check_health_if_due();
+        self.call(self.read_order(), call)
+    }
+
+    /// Reads from all the healthy endpoints, merging their answers into the most advanced one.
+    fn read_all<T: Send>(
+        &self,
+        call: impl Fn(&B) -> ClientResult<T> + Sync,
+        merge: impl Fn(T, T) -> T,
+    ) -> ClientResult<T> {
+        self.check_health_if_due();
+        self.fan_out(self.all_healthy_order(), call, merge)
+    }
+
+    fn primary<T>(&self, call: impl Fn(&B) -> ClientResult<T>) -> ClientResult<T> {
+        self.check_health_if_due();
+        self.call(self.primary_order(), call)
+    }
+
+    fn send(&self, send: impl Fn(&B) -> ClientResult<Signature> + Sync) -> ClientResult<Signature> {
+        self.check_health_if_due();
+        self.fan_out(self.send_order(), send, |first, _| first)
+    }
+}
+
+impl<B: Backend + Sync> Backend for FailoverBackend<B> {
+    fn commitment(&self) -> CommitmentConfig {
+        self.endpoints[0].commitment()
+    }
+
+    fn url(&self) -> Option<String> {
+        self.endpoints[0].url()
+    }
+
+    fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> ClientResult<u64> {
+        self.read(|endpoint| endpoint.get_minimum_balance_for_rent_exemption(data_len))
+    }
+
+    fn get_latest_blockhash_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<(Hash, u64)> {
+        self.read(|endpoint| endpoint.get_latest_blockhash_with_commitment(commitment))
+    }
+
+    fn is_blockhash_valid(&self, blockhash: &Hash, commitment: CommitmentConfig) -> ClientResult<bool> {
+        self.read_all(
+            |endpoint| endpoint.is_blockhash_valid(blockhash, commitment),
+            |valid, other| valid || other,
+        )
+    }
+
+    fn get_slot_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<Slot> {
+        self.read(|endpoint| endpoint.get_slot_with_commitment(commitment))
+    }
+
+    fn get_account_with_commitment(
+        &self,
+        pubkey: &Pubkey,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<Account>> {
+        self.read(|endpoint| endpoint.get_account_with_commitment(pubkey, commitment))
+    }
+
+    fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        self.read(|endpoint| endpoint.get_multiple_accounts(pubkeys))
+    }
+
+    fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> ClientResult<Vec<u64>> {
+        self.read(|endpoint| endpoint.get_recent_prioritization_fees(accounts))
+    }
+
+    fn get_fee_for_message(&self, message: &VersionedMessage) -> ClientResult<Option<u64>> {
+        self.read(|endpoint| endpoint.get_fee_for_message(message))
+    }
+
+    fn send_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        self.send(|endpoint| endpoint.send_transaction(transaction))
+    }
+
+    fn resend_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        self.send(|endpoint| endpoint.resend_transaction(transaction))
+    }
+
+    fn get_signature_status_with_commitment(
+        &self,
+        signature: &Signature,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionStatus>> {
+        self.read_all(
+            |endpoint| endpoint.get_signature_status_with_commitment(signature, commitment),
+            latest_status,
+        )
+    }
+
+    fn get_transaction_details(
+        &self,
+        signature: &Signature,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionDetails>> {
+        self.read(|endpoint| endpoint.get_transaction_details(signature, commitment))
+    }
+
+    fn simulate_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        accounts: &[Pubkey],
+    ) -> ClientResult<RpcSimulateTransactionResult> {
+        self.read(|endpoint| endpoint.simulate_transaction(transaction, accounts))
+    }
+
+    fn airdrop(&self, to_pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature> {
+        self.primary(|endpoint| endpoint.airdrop(to_pubkey, lamports))
+    }
+}
+
+impl<B: AsyncBackend> FailoverBackend<B> {
+    /// Async counterpart of [`FailoverBackend::check_health`].
+    pub async fn check_health_async(&self) {
+        let slots = join_all(
+            self.endpoints
+                .iter()
+                .map(|endpoint| endpoint.get_slot_with_commitment(endpoint.commitment())),
+        )
+        .await;
+        for (index, slot) in slots.into_iter().enumerate() {
+            self.record_slot(index, slot);
+        }
+    }
+
+    async fn check_health_if_due_async(&self) {
+        if self.take_health_check() {
+            self.check_health_async().await;
+        }
+    }
+
+    async fn call_async<'a, T>(
+        &'a self,
+        order: Vec<usize>,
+        call: impl Fn(&'a B) -> BackendFuture<'a, T> + Send,
+    ) -> ClientResult<T> {
+        let mut last_error = None;
+        for index in order {
+            let result = call(&self.endpoints[index]).await;
+            match self.record(index, result) {
+                Err(error) if is_endpoint_error(&error) => last_error = Some(error),
+                result => return result,
+            }
+        }
+        Err(last_error.expect("at least one endpoint is called"))
+    }
+
+    /// Async counterpart of [`FailoverBackend::fan_out`].
+    async fn fan_out_async<'a, T>(
+        &'a self,
+        (at_once, fallback): (Vec<usize>, Vec<usize>),
+        call: impl Fn(&'a B) -> BackendFuture<'a, T> + Send,
+        merge: impl Fn(T, T) -> T + Send,
+    ) -> ClientResult<T> {
+        let results = join_all(at_once.iter().map(|index| call(&self.endpoints[*index]))).await;
+        let results = at_once
+            .iter()
+            .zip(results)
+            .map(|(index, result)| self.record(*index, result))
+            .collect();
+
+        match merge_results(results, merge) {
+            Some(Err(error)) if is_endpoint_error(&error) && !fallback.is_empty() => {
+                self.call_async(fallback, call).await
+            },
+            Some(result) => result,
+            None => self.call_async(fallback, call).await,
+        }
+    }
+
+    async fn read_async<'a, T>(&'a self, call: impl Fn(&'a B) -> BackendFuture<'a, T> + Send) -> ClientResult<T> {
+        self.check_health_if_due_async().await;
+        self.call_async(self.read_order(), call).await
+    }
+
+    async fn read_all_async<'a, T>(
+        &'a self,
+        call: impl Fn(&'a B) -> BackendFuture<'a, T> + Send,
+        merge: impl Fn(T, T) -> T + Send,
+    ) -> ClientResult<T> {
+        self.check_health_if_due_async().await;
+        self.fan_out_async(self.all_healthy_order(), call, merge).await
+    }
+
+    async fn primary_async<'a, T>(&'a self, call: impl Fn(&'a B) -> BackendFuture<'a, T> + Send) -> ClientResult<T> {
+        self.check_health_if_due_async().await;
+        self.call_async(self.primary_order(), call).await
+    }
+
+    async fn send_async<'a>(
+        &'a self,
+        send: impl Fn(&'a B) -> BackendFuture<'a, Signature> + Send,
+    ) -> ClientResult<Signature> {
+        self.check_health_if_due_async().await;
+        self.fan_out_async(self.send_order(), send, |first, _| first).await
+    }
+}
+
+#[async_trait]
+impl<B: AsyncBackend> AsyncBackend for FailoverBackend<B> {
+    fn commitment(&self) -> CommitmentConfig {
+        self.endpoints[0].commitment()
+    }
+
+    async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> ClientResult<u64> {
+        self.read_async(|endpoint| endpoint.get_minimum_balance_for_rent_exemption(data_len))
+            .await
+    }
+
+    async fn get_latest_blockhash_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<(Hash, u64)> {
+        self.read_async(|endpoint| endpoint.get_latest_blockhash_with_commitment(commitment))
+            .await
+    }
+
+    async fn is_blockhash_valid(&self, blockhash: &Hash, commitment: CommitmentConfig) -> ClientResult<bool> {
+        self.read_all_async(
+            |endpoint| endpoint.is_blockhash_valid(blockhash, commitment),
+            |valid, other| valid || other,
+        )
+        .await
+    }
+
+    async fn get_slot_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<Slot> {
+        self.read_async(|endpoint| endpoint.get_slot_with_commitment(commitment))
+            .await
+    }
+
+    async fn get_account_with_commitment(
+        &self,
+        pubkey: &Pubkey,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<Account>> {
+        self.read_async(|endpoint| endpoint.get_account_with_commitment(pubkey, commitment))
+            .await
+    }
+
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        self.read_async(|endpoint| endpoint.get_multiple_accounts(pubkeys))
+            .await
+    }
+
+    async fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> ClientResult<Vec<u64>> {
+        self.read_async(|endpoint| endpoint.get_recent_prioritization_fees(accounts))
+            .await
+    }
+
+    async fn get_fee_for_message(&self, message: &VersionedMessage) -> ClientResult<Option<u64>> {
+        self.read_async(|endpoint| endpoint.get_fee_for_message(message)).await
+    }
+
+    async fn send_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        self.send_async(|endpoint| endpoint.send_transaction(transaction)).await
+    }
+
+    async fn resend_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        self.send_async(|endpoint| endpoint.resend_transaction(transaction))
+            .await
+    }
+
+    async fn get_signature_status_with_commitment(
+        &self,
+        signature: &Signature,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionStatus>> {
+        self.read_all_async(
+            |endpoint| endpoint.get_signature_status_with_commitment(signature, commitment),
+            latest_status,
+        )
+        .await
+    }
+
+    async fn get_transaction_details(
+        &self,
+        signature: &Signature,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionDetails>> {
+        self.read_async(|endpoint| endpoint.get_transaction_details(signature, commitment))
+            .await
+    }
+
+    async fn simulate_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        accounts: &[Pubkey],
+    ) -> ClientResult<RpcSimulateTransactionResult> {
+        self.read_async(|endpoint| endpoint.simulate_transaction(transaction, accounts))
+            .await
+    }
+
+    async fn airdrop(&self, to_pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature> {
+        self.primary_async(|endpoint| endpoint.airdrop(to_pubkey, lamports))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{self, BufRead, BufReader, Read, Write},
+        net::{TcpListener, TcpStream},
+        sync::{
+            atomic::{AtomicBool, AtomicU64},
+            Arc,
+        },
+    };
+
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    /// Local JSON-RPC endpoint answering its version, its slot and the rent exemption, the latter with a server
+    /// error while `failing` is set.
+    struct StandIn {
+        url: String,
+        slot: Arc<AtomicU64>,
+        failing: Arc<AtomicBool>,
+    }
+
+    impl StandIn {
+        /// Starts the endpoint, which answers the rent exemption with `rent` lamports to tell it apart.
+        fn start(slot: Slot, rent: u64) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let slot = Arc::new(AtomicU64::new(slot));
+            let failing = Arc::new(AtomicBool::new(false));
+
+            let (endpoint_slot, endpoint_failing) = (slot.clone(), failing.clone());
+            thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    let _ = respond(stream, &endpoint_slot, &endpoint_failing, rent);
+                }
+            });
+
+            Self { url, slot, failing }
+        }
+
+        fn client(&self) -> RpcClient {
+            RpcClient::new_with_commitment(self.url.clone(), CommitmentConfig::confirmed())
+        }
+    }
+
+    fn respond(mut stream: TcpStream, slot: &AtomicU64, failing: &AtomicBool, rent: u64) -> io::Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or_default();
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+        let request: Value = serde_json::from_slice(&body)?;
+
+        let result = match request["method"].as_str() {
+            Some("getVersion") => Some(json!({ "solana-core": "1.16.0", "feature-set": 0 })),
+            Some("getSlot") => Some(json!(slot.load(Ordering::SeqCst))),
+            Some("getMinimumBalanceForRentExemption") if !failing.load(Ordering::SeqCst) => Some(json!(rent)),
+            _ => None,
+        };
+        match result {
+            Some(result) => {
+                let body = json!({ "jsonrpc": "2.0", "result": result, "id": request["id"] }).to_string();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            },
+            None => write!(
+                stream,
+                "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            ),
+        }
+    }
+
+    fn backend(endpoints: &[&StandIn], check_interval: Duration) -> FailoverBackend {
+        FailoverBackend::new(endpoints.iter().map(|endpoint| endpoint.client()))
+            .unwrap()
+            .with_health_config(HealthConfig {
+                check_interval,
+                ..HealthConfig::default()
+            })
+    }
+
+    fn read_rent(backend: &FailoverBackend) -> u64 {
+        Backend::get_minimum_balance_for_rent_exemption(backend, 0).unwrap()
+    }
+
+    #[test]
+    fn no_endpoints() {
+        assert!(matches!(
+            FailoverBackend::<RpcClient>::new(Vec::new()),
+            Err(ClientError::MissingEndpoints)
+        ));
+    }
+
+    #[test]
+    fn reads_fail_over_to_next_endpoint() {
+        let failing = StandIn::start(100, 1);
+        failing.failing.store(true, Ordering::SeqCst);
+        let healthy = StandIn::start(100, 2);
+        let backend = backend(&[&failing, &healthy], Duration::from_secs(3600));
+
+        for _ in 0..4 {
+            assert_eq!(read_rent(&backend), 2);
+        }
+    }
+
+    #[test]
+    fn lagging_endpoint_is_cut_off() {
+        let leading = StandIn::start(1000, 1);
+        let lagging = StandIn::start(900, 2);
+        let backend = backend(&[&leading, &lagging], Duration::from_secs(3600));
+
+        backend.check_health();
+        assert_eq!(backend.healthy_endpoints(), vec![0]);
+        for _ in 0..4 {
+            assert_eq!(read_rent(&backend), 1);
+        }
+
+        lagging.slot.store(950, Ordering::SeqCst);
+        backend.check_health();
+        assert_eq!(backend.healthy_endpoints(), vec![0, 1]);
+    }
+
+    #[test]
+    fn lagging_endpoint_recovers() {
+        let leading = StandIn::start(1000, 1);
+        let lagging = StandIn::start(900, 2);
+        let backend = backend(&[&leading, &lagging], Duration::ZERO);
+
+        assert_eq!(read_rent(&backend), 1);
+        assert_eq!(read_rent(&backend), 1);
+
+        lagging.slot.store(1000, Ordering::SeqCst);
+        let mut rents = vec![read_rent(&backend), read_rent(&backend)];
+        rents.sort_unstable();
+        assert_eq!(rents, vec![1, 2]);
+    }
+
+    #[test]
+    fn latest_status_is_most_advanced() {
+        let status = |slot, confirmation_status| TransactionStatus {
+            slot,
+            confirmations: Some(0),
+            status: Ok(()),
+            err: None,
+            confirmation_status: Some(confirmation_status),
+        };
+        let processed = status(12, TransactionConfirmationStatus::Processed);
+        let confirmed = status(10, TransactionConfirmationStatus::Confirmed);
+
+        assert_eq!(latest_status(None, Some(processed.clone())), Some(processed.clone()));
+        assert_eq!(
+            latest_status(Some(confirmed.clone()), Some(processed.clone())),
+            Some(confirmed.clone())
+        );
+        assert_eq!(latest_status(Some(processed), Some(confirmed.clone())), Some(confirmed));
+    }
+}
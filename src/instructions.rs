@@ -0,0 +1,36 @@
+//! Instruction builders shared by the blocking and nonblocking helpers.
+//!
+//! Each builder is a pure function, so the resulting instructions can be signed and sent elsewhere.
+
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+pub mod lookup_table;
+pub mod swap;
+pub mod system;
+pub mod token;
+
+/// Instructions of a single helper operation together with the pubkeys required to sign them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstructionSet {
+    pub instructions: Vec<Instruction>,
+    pub signers: Vec<Pubkey>,
+}
+
+impl InstructionSet {
+    pub fn new(instructions: Vec<Instruction>) -> Self {
+        let mut signers = Vec::new();
+        for account in instructions.iter().flat_map(|instruction| &instruction.accounts) {
+            if account.is_signer && !signers.contains(&account.pubkey) {
+                signers.push(account.pubkey);
+            }
+        }
+
+        Self { instructions, signers }
+    }
+}
+
+impl From<Vec<Instruction>> for InstructionSet {
+    fn from(instructions: Vec<Instruction>) -> Self {
+        Self::new(instructions)
+    }
+}
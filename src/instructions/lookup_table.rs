@@ -0,0 +1,42 @@
+use solana_address_lookup_table_program::instruction as lookup_table_instruction;
+use solana_sdk::{clock::Slot, pubkey::Pubkey};
+
+use crate::instructions::InstructionSet;
+
+/// Number of addresses added by a single extend instruction, so that the transaction fits into a packet.
+pub const MAX_EXTEND_ADDRESSES: usize = 20;
+
+/// Returns the instructions creating the lookup table together with its address derived from the recent slot.
+pub fn create_lookup_table(authority: &Pubkey, payer: &Pubkey, recent_slot: Slot) -> (InstructionSet, Pubkey) {
+    let (instruction, lookup_table) = lookup_table_instruction::create_lookup_table(*authority, *payer, recent_slot);
+    (InstructionSet::new(vec![instruction]), lookup_table)
+}
+
+pub fn extend_lookup_table(
+    lookup_table: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    addresses: &[Pubkey],
+) -> InstructionSet {
+    InstructionSet::new(vec![lookup_table_instruction::extend_lookup_table(
+        *lookup_table,
+        *authority,
+        Some(*payer),
+        addresses.to_vec(),
+    )])
+}
+
+pub fn deactivate_lookup_table(lookup_table: &Pubkey, authority: &Pubkey) -> InstructionSet {
+    InstructionSet::new(vec![lookup_table_instruction::deactivate_lookup_table(
+        *lookup_table,
+        *authority,
+    )])
+}
+
+pub fn close_lookup_table(lookup_table: &Pubkey, authority: &Pubkey, recipient: &Pubkey) -> InstructionSet {
+    InstructionSet::new(vec![lookup_table_instruction::close_lookup_table(
+        *lookup_table,
+        *authority,
+        *recipient,
+    )])
+}
@@ -0,0 +1,173 @@
+use solana_sdk::{program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, system_instruction};
+use spl_token::{native_mint, state::Account as TokenAccount};
+use spl_token_swap::{
+    curve::{base::SwapCurve, fees::Fees},
+    instruction::Swap,
+};
+
+use crate::instructions::{token, InstructionSet};
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_swap(
+    payer: &Pubkey,
+    swap_program_id: &Pubkey,
+    swap_account: &Pubkey,
+    swap_authority_address: &Pubkey,
+    swap_authority_nonce: u8,
+    pool_token_mint_address: &Pubkey,
+    token_a_address: &Pubkey,
+    token_b_address: &Pubkey,
+    fee_account: &Pubkey,
+    pool_token_initial_supply_account: &Pubkey,
+    owner_address: &Pubkey,
+    fees: Fees,
+    fee_account_owner_address: &Pubkey,
+    token_account_lamports: u64,
+) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![
+        system_instruction::create_account(
+            payer,
+            fee_account,
+            token_account_lamports,
+            TokenAccount::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_account(
+            &spl_token::id(),
+            fee_account,
+            pool_token_mint_address,
+            fee_account_owner_address,
+        )?,
+        system_instruction::create_account(
+            payer,
+            pool_token_initial_supply_account,
+            token_account_lamports,
+            TokenAccount::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_account(
+            &spl_token::id(),
+            pool_token_initial_supply_account,
+            pool_token_mint_address,
+            owner_address,
+        )?,
+        spl_token_swap::instruction::initialize(
+            swap_program_id,
+            &spl_token::id(),
+            swap_account,
+            swap_authority_address,
+            token_a_address,
+            token_b_address,
+            pool_token_mint_address,
+            fee_account,
+            pool_token_initial_supply_account,
+            swap_authority_nonce,
+            fees,
+            SwapCurve::default(),
+        )?,
+    ]))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn swap(
+    swap_program_id: &Pubkey,
+    swap_account_address: &Pubkey,
+    swap_authority_address: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    source_address: &Pubkey,
+    pool_source_address: &Pubkey,
+    pool_destination_address: &Pubkey,
+    destination_address: &Pubkey,
+    pool_token_mint_address: &Pubkey,
+    fee_account_address: &Pubkey,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![spl_token_swap::instruction::swap(
+        swap_program_id,
+        &spl_token::id(),
+        swap_account_address,
+        swap_authority_address,
+        user_transfer_authority,
+        source_address,
+        pool_source_address,
+        pool_destination_address,
+        destination_address,
+        pool_token_mint_address,
+        fee_account_address,
+        None,
+        Swap {
+            amount_in,
+            minimum_amount_out,
+        },
+    )?]))
+}
+
+/// Swaps SOL of the user through temporary wrapped SOL accounts. When `wrap_source` is set, the source account is
+/// created holding `amount_in` lamports of the user, and when `unwrap_destination` is, the destination account is
+/// created to receive the swapped SOL. The user funds both accounts, which are closed to the user after the swap.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_wrapping_sol(
+    swap_program_id: &Pubkey,
+    swap_account_address: &Pubkey,
+    swap_authority_address: &Pubkey,
+    user: &Pubkey,
+    source_address: &Pubkey,
+    pool_source_address: &Pubkey,
+    pool_destination_address: &Pubkey,
+    destination_address: &Pubkey,
+    pool_token_mint_address: &Pubkey,
+    fee_account_address: &Pubkey,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    wrap_source: bool,
+    unwrap_destination: bool,
+    token_account_lamports: u64,
+) -> Result<InstructionSet, ProgramError> {
+    let mut instructions = Vec::new();
+    if wrap_source {
+        let lamports = token_account_lamports
+            .checked_add(amount_in)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        instructions.extend(
+            token::create_token_account(user, source_address, user, &native_mint::id(), lamports)?.instructions,
+        );
+    }
+    if unwrap_destination {
+        instructions.extend(
+            token::create_token_account(
+                user,
+                destination_address,
+                user,
+                &native_mint::id(),
+                token_account_lamports,
+            )?
+            .instructions,
+        );
+    }
+    instructions.extend(
+        swap(
+            swap_program_id,
+            swap_account_address,
+            swap_authority_address,
+            user,
+            source_address,
+            pool_source_address,
+            pool_destination_address,
+            destination_address,
+            pool_token_mint_address,
+            fee_account_address,
+            amount_in,
+            minimum_amount_out,
+        )?
+        .instructions,
+    );
+    if wrap_source {
+        instructions.extend(token::unwrap_sol(user, &[], source_address, user)?.instructions);
+    }
+    if unwrap_destination {
+        instructions.extend(token::unwrap_sol(user, &[], destination_address, user)?.instructions);
+    }
+
+    Ok(InstructionSet::new(instructions))
+}
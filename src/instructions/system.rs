@@ -0,0 +1,62 @@
+use solana_sdk::{pubkey::Pubkey, system_instruction};
+
+use crate::instructions::InstructionSet;
+
+pub fn create_account(
+    payer: &Pubkey,
+    account: &Pubkey,
+    owner: &Pubkey,
+    account_data_len: usize,
+    lamports: u64,
+) -> InstructionSet {
+    InstructionSet::new(vec![system_instruction::create_account(
+        payer,
+        account,
+        lamports,
+        account_data_len as u64,
+        owner,
+    )])
+}
+
+pub fn create_nonce_account(
+    payer: &Pubkey,
+    nonce_account: &Pubkey,
+    authority: &Pubkey,
+    lamports: u64,
+) -> InstructionSet {
+    InstructionSet::new(system_instruction::create_nonce_account(
+        payer,
+        nonce_account,
+        authority,
+        lamports,
+    ))
+}
+
+pub fn advance_nonce_account(nonce_account: &Pubkey, authority: &Pubkey) -> InstructionSet {
+    InstructionSet::new(vec![system_instruction::advance_nonce_account(
+        nonce_account,
+        authority,
+    )])
+}
+
+pub fn withdraw_nonce_account(
+    nonce_account: &Pubkey,
+    authority: &Pubkey,
+    destination: &Pubkey,
+    lamports: u64,
+) -> InstructionSet {
+    InstructionSet::new(vec![system_instruction::withdraw_nonce_account(
+        nonce_account,
+        authority,
+        destination,
+        lamports,
+    )])
+}
+
+pub fn authorize_nonce_account(nonce_account: &Pubkey, authority: &Pubkey, new_authority: &Pubkey) -> InstructionSet {
+    InstructionSet::new(vec![system_instruction::authorize_nonce_account(
+        nonce_account,
+        authority,
+        new_authority,
+    )])
+}
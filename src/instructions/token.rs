@@ -0,0 +1,256 @@
+//! Builders of the token program instructions.
+//!
+//! The builders taking an authority also take the pubkeys signing for it when the authority is a multisig account,
+//! which are empty for an authority signing by itself.
+
+use solana_sdk::{program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, system_instruction};
+use spl_token::{
+    instruction::AuthorityType,
+    state::{Account as TokenAccount, Mint, Multisig},
+};
+
+use crate::instructions::InstructionSet;
+
+pub fn create_token_mint(
+    payer: &Pubkey,
+    token_mint: &Pubkey,
+    owner: &Pubkey,
+    decimals: u8,
+    lamports: u64,
+) -> Result<InstructionSet, ProgramError> {
+    create_token_mint_with_freeze_authority(payer, token_mint, owner, None, decimals, lamports)
+}
+
+pub fn create_token_mint_with_freeze_authority(
+    payer: &Pubkey,
+    token_mint: &Pubkey,
+    owner: &Pubkey,
+    freeze_authority: Option<&Pubkey>,
+    decimals: u8,
+    lamports: u64,
+) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![
+        system_instruction::create_account(payer, token_mint, lamports, Mint::LEN as u64, &spl_token::id()),
+        spl_token::instruction::initialize_mint(&spl_token::id(), token_mint, owner, freeze_authority, decimals)?,
+    ]))
+}
+
+pub fn create_token_account(
+    payer: &Pubkey,
+    token_account: &Pubkey,
+    owner: &Pubkey,
+    token_mint: &Pubkey,
+    lamports: u64,
+) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![
+        system_instruction::create_account(
+            payer,
+            token_account,
+            lamports,
+            TokenAccount::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_account(&spl_token::id(), token_account, token_mint, owner)?,
+    ]))
+}
+
+/// Creates an `m` of `signers` multisig account, which can be used as the authority of mints and token accounts.
+pub fn create_multisig(
+    payer: &Pubkey,
+    multisig: &Pubkey,
+    signers: &[&Pubkey],
+    m: u8,
+    lamports: u64,
+) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![
+        system_instruction::create_account(payer, multisig, lamports, Multisig::LEN as u64, &spl_token::id()),
+        spl_token::instruction::initialize_multisig(&spl_token::id(), multisig, signers, m)?,
+    ]))
+}
+
+pub fn mint_to(
+    owner: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    token_mint: &Pubkey,
+    account: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![spl_token::instruction::mint_to_checked(
+        &spl_token::id(),
+        token_mint,
+        account,
+        owner,
+        multisig_signers,
+        amount,
+        decimals,
+    )?]))
+}
+
+pub fn transfer_to(
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    token_mint: &Pubkey,
+    source: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![spl_token::instruction::transfer_checked(
+        &spl_token::id(),
+        source,
+        token_mint,
+        destination,
+        authority,
+        multisig_signers,
+        amount,
+        decimals,
+    )?]))
+}
+
+/// Allows the delegate to transfer or burn up to `amount` tokens of the account.
+pub fn approve(
+    owner: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    token_mint: &Pubkey,
+    account: &Pubkey,
+    delegate: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![spl_token::instruction::approve_checked(
+        &spl_token::id(),
+        account,
+        token_mint,
+        delegate,
+        owner,
+        multisig_signers,
+        amount,
+        decimals,
+    )?]))
+}
+
+pub fn revoke(owner: &Pubkey, multisig_signers: &[&Pubkey], account: &Pubkey) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![spl_token::instruction::revoke(
+        &spl_token::id(),
+        account,
+        owner,
+        multisig_signers,
+    )?]))
+}
+
+/// Burns tokens of the account, signed by its owner or delegate.
+pub fn burn(
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    token_mint: &Pubkey,
+    account: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![spl_token::instruction::burn_checked(
+        &spl_token::id(),
+        account,
+        token_mint,
+        authority,
+        multisig_signers,
+        amount,
+        decimals,
+    )?]))
+}
+
+pub fn freeze_account(
+    freeze_authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    token_mint: &Pubkey,
+    account: &Pubkey,
+) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![spl_token::instruction::freeze_account(
+        &spl_token::id(),
+        account,
+        token_mint,
+        freeze_authority,
+        multisig_signers,
+    )?]))
+}
+
+pub fn thaw_account(
+    freeze_authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    token_mint: &Pubkey,
+    account: &Pubkey,
+) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![spl_token::instruction::thaw_account(
+        &spl_token::id(),
+        account,
+        token_mint,
+        freeze_authority,
+        multisig_signers,
+    )?]))
+}
+
+/// Changes an authority of a mint or a token account, `None` removing it for good.
+pub fn set_authority(
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    owned: &Pubkey,
+    authority_type: AuthorityType,
+    new_authority: Option<&Pubkey>,
+) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![spl_token::instruction::set_authority(
+        &spl_token::id(),
+        owned,
+        new_authority,
+        authority_type,
+        authority,
+        multisig_signers,
+    )?]))
+}
+
+pub fn create_associated_token_account(funder: &Pubkey, recipient: &Pubkey, token_mint: &Pubkey) -> InstructionSet {
+    InstructionSet::new(vec![spl_associated_token_account::create_associated_token_account(
+        funder, recipient, token_mint,
+    )])
+}
+
+/// Moves lamports of the owner into a token account of the native mint, so that they can be used as wrapped SOL.
+pub fn wrap_sol(owner: &Pubkey, account: &Pubkey, lamports: u64) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![
+        system_instruction::transfer(owner, account, lamports),
+        spl_token::instruction::sync_native(&spl_token::id(), account)?,
+    ]))
+}
+
+/// Updates the amount of a token account of the native mint to the lamports it holds above the rent exemption.
+pub fn sync_native(account: &Pubkey) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![spl_token::instruction::sync_native(
+        &spl_token::id(),
+        account,
+    )?]))
+}
+
+/// Unwraps the wrapped SOL of a token account of the native mint by closing it, all of its lamports going to the
+/// destination.
+pub fn unwrap_sol(
+    owner: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    account: &Pubkey,
+    destination: &Pubkey,
+) -> Result<InstructionSet, ProgramError> {
+    close_token_account(owner, multisig_signers, account, destination)
+}
+
+pub fn close_token_account(
+    owner: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    account: &Pubkey,
+    destination: &Pubkey,
+) -> Result<InstructionSet, ProgramError> {
+    Ok(InstructionSet::new(vec![spl_token::instruction::close_account(
+        &spl_token::id(),
+        account,
+        destination,
+        owner,
+        multisig_signers,
+    )?]))
+}
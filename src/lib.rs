@@ -14,7 +14,7 @@
 //!     let recipient = Keypair::new();
 //!
 //!     let client = RpcClient::new_with_commitment("http://localhost:8899".into(), CommitmentConfig::confirmed());
-//!     let client = Client { client, payer };
+//!     let client = Client::new(client, payer);
 //!
 //!     client.airdrop(&client.payer_pubkey(), 10_000_000_000)?;
 //!     assert_eq!(client.get_balance(&client.payer_pubkey())?, 10_000_000_000);
@@ -45,9 +45,36 @@ pub use spl_associated_token_account;
 pub use spl_token;
 pub use spl_token_swap;
 
-pub use crate::{client::*, print::*, swap::*, token::*};
+pub use crate::{
+    amount::*, backend::*, client::*, client_builder::*, compute_budget::*, confirmation::*, error::*, lookup_table::*,
+    multisig::*, nonce::*, print::*, receipt::*, retry::*, simulation::*, subscription::*, swap::*, token::*,
+    transaction_builder::*,
+};
 
+pub mod amount;
+pub mod backend;
+#[cfg(feature = "banks")]
+pub mod banks;
+pub mod blockhash;
 pub mod client;
+pub mod client_builder;
+pub mod compute_budget;
+pub mod confirmation;
+pub mod error;
+pub mod failover;
+pub mod instructions;
+pub mod lookup_table;
+pub mod mock;
+pub mod multisig;
+pub mod nonblocking;
+pub mod nonce;
 pub mod print;
+pub mod rate_limit;
+pub mod receipt;
+pub mod retry;
+pub mod simulation;
+pub mod subscription;
 pub mod swap;
+pub mod telemetry;
 pub mod token;
+pub mod transaction_builder;
@@ -0,0 +1,200 @@
+use solana_address_lookup_table_program::state::AddressLookupTable;
+use solana_sdk::{
+    account::Account, address_lookup_table_account::AddressLookupTableAccount, commitment_config::CommitmentConfig,
+    pubkey::Pubkey, signature::Signer,
+};
+
+use crate::{
+    backend::Backend,
+    instructions::lookup_table::{self as instructions, MAX_EXTEND_ADDRESSES},
+    Client, ClientError, ClientResult, TransactionReceipt,
+};
+
+/// Decodes the addresses stored in the lookup table account.
+pub fn lookup_table_account(lookup_table: &Pubkey, account: &Account) -> ClientResult<AddressLookupTableAccount> {
+    if account.owner != solana_address_lookup_table_program::id() {
+        return Err(ClientError::InvalidLookupTable(*lookup_table));
+    }
+    let table =
+        AddressLookupTable::deserialize(&account.data).map_err(|_| ClientError::InvalidLookupTable(*lookup_table))?;
+
+    Ok(AddressLookupTableAccount {
+        key: *lookup_table,
+        addresses: table.addresses.to_vec(),
+    })
+}
+
+pub trait AddressLookupTables {
+    fn create_lookup_table(&self, authority: &Pubkey) -> ClientResult<Pubkey>;
+    fn get_lookup_table(&self, lookup_table: &Pubkey) -> ClientResult<AddressLookupTableAccount>;
+    fn extend_lookup_table(
+        &self,
+        lookup_table: &Pubkey,
+        authority: &dyn Signer,
+        addresses: &[Pubkey],
+    ) -> ClientResult<Vec<TransactionReceipt>>;
+    fn deactivate_lookup_table(
+        &self,
+        lookup_table: &Pubkey,
+        authority: &dyn Signer,
+    ) -> ClientResult<TransactionReceipt>;
+    fn close_lookup_table(
+        &self,
+        lookup_table: &Pubkey,
+        authority: &dyn Signer,
+        recipient: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+}
+
+impl<B: Backend> AddressLookupTables for Client<B> {
+    fn create_lookup_table(&self, authority: &Pubkey) -> ClientResult<Pubkey> {
+        let recent_slot = self.get_slot_with_commitment(CommitmentConfig::finalized())?;
+        let (instructions, lookup_table) =
+            instructions::create_lookup_table(authority, &self.payer_pubkey(), recent_slot);
+        self.process_instructions(&instructions.instructions, &[])?;
+
+        Ok(lookup_table)
+    }
+
+    fn get_lookup_table(&self, lookup_table: &Pubkey) -> ClientResult<AddressLookupTableAccount> {
+        let account = self
+            .get_account_with_commitment(lookup_table, self.commitment())?
+            .ok_or(ClientError::AccountNotFound(*lookup_table))?;
+        lookup_table_account(lookup_table, &account)
+    }
+
+    /// Adds the addresses to the lookup table, sending a transaction per [`MAX_EXTEND_ADDRESSES`] of them.
+    ///
+    /// The new addresses can be used by transactions starting with the next slot.
+    fn extend_lookup_table(
+        &self,
+        lookup_table: &Pubkey,
+        authority: &dyn Signer,
+        addresses: &[Pubkey],
+    ) -> ClientResult<Vec<TransactionReceipt>> {
+        addresses
+            .chunks(MAX_EXTEND_ADDRESSES)
+            .map(|addresses| {
+                let instructions = instructions::extend_lookup_table(
+                    lookup_table,
+                    &authority.pubkey(),
+                    &self.payer_pubkey(),
+                    addresses,
+                );
+                self.process_instructions(&instructions.instructions, &[authority])
+            })
+            .collect()
+    }
+
+    fn deactivate_lookup_table(
+        &self,
+        lookup_table: &Pubkey,
+        authority: &dyn Signer,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::deactivate_lookup_table(lookup_table, &authority.pubkey());
+        self.process_instructions(&instructions.instructions, &[authority])
+    }
+
+    /// Closes the deactivated lookup table, which is only possible once its deactivation slot is no longer recent.
+    fn close_lookup_table(
+        &self,
+        lookup_table: &Pubkey,
+        authority: &dyn Signer,
+        recipient: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::close_lookup_table(lookup_table, &authority.pubkey(), recipient);
+        self.process_instructions(&instructions.instructions, &[authority])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use solana_address_lookup_table_program::{
+        instruction::{derive_lookup_table_address, ProgramInstruction},
+        state::LookupTableMeta,
+    };
+    use solana_sdk::{message::VersionedMessage, signature::Keypair, system_instruction};
+
+    use super::*;
+    use crate::mock::MockBackend;
+
+    fn lookup_table(addresses: &[Pubkey]) -> Account {
+        let table = AddressLookupTable {
+            meta: LookupTableMeta::default(),
+            addresses: Cow::Borrowed(addresses),
+        };
+        Account {
+            lamports: 1_000_000,
+            data: table.serialize_for_tests().unwrap(),
+            owner: solana_address_lookup_table_program::id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn lookup_table_is_created_at_recent_slot() {
+        let client = Client::new(MockBackend::new(), Keypair::new());
+        let authority = Pubkey::new_unique();
+
+        let lookup_table = client.create_lookup_table(&authority).unwrap();
+
+        assert_eq!(lookup_table, derive_lookup_table_address(&authority, 0).0);
+        let instructions = client.instructions();
+        assert_eq!(instructions.len(), 1);
+        assert_eq!(instructions[0].accounts[0], lookup_table);
+        assert!(matches!(
+            instructions[0].lookup_table(),
+            Some(ProgramInstruction::CreateLookupTable { recent_slot: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn lookup_table_is_extended_in_chunks() {
+        let client = Client::new(MockBackend::new(), Keypair::new());
+        let (lookup_table, authority) = (Pubkey::new_unique(), Keypair::new());
+        let addresses: Vec<_> = (0..45).map(|_| Pubkey::new_unique()).collect();
+
+        let receipts = client
+            .extend_lookup_table(&lookup_table, &authority, &addresses)
+            .unwrap();
+
+        assert_eq!(receipts.len(), 3);
+        assert_eq!(client.transactions().len(), 3);
+        let extended: Vec<_> = client
+            .instructions()
+            .iter()
+            .map(|instruction| match instruction.lookup_table() {
+                Some(ProgramInstruction::ExtendLookupTable { new_addresses }) => new_addresses,
+                instruction => panic!("unexpected instruction {instruction:?}"),
+            })
+            .collect();
+        let chunks: Vec<_> = addresses.chunks(MAX_EXTEND_ADDRESSES).collect();
+        assert_eq!(extended, chunks);
+    }
+
+    #[test]
+    fn transaction_references_accounts_of_lookup_table() {
+        let (lookup_table_key, recipient) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let backend = MockBackend::new().with_account(lookup_table_key, lookup_table(&[recipient]));
+        let client = Client::new(backend, Keypair::new());
+
+        let lookup_table = client.get_lookup_table(&lookup_table_key).unwrap();
+        assert_eq!(lookup_table.addresses, [recipient]);
+
+        let transfer = system_instruction::transfer(&client.payer_pubkey(), &recipient, 100);
+        client
+            .process_instructions_with_lookup_tables(&[transfer], &[], &[lookup_table])
+            .unwrap();
+
+        let message = &client.transactions()[0].message;
+        assert!(matches!(message, VersionedMessage::V0(_)));
+        assert!(!message.static_account_keys().contains(&recipient));
+        let lookups = message.address_table_lookups().unwrap();
+        assert_eq!(lookups.len(), 1);
+        assert_eq!(lookups[0].account_key, lookup_table_key);
+        assert_eq!(lookups[0].writable_indexes, [0]);
+    }
+}
@@ -0,0 +1,457 @@
+//! Backend which records the transactions of the helpers instead of executing them, for unit tests.
+//!
+//! ```
+//! use solana_client_helpers::{mock::MockBackend, spl_token::instruction::TokenInstruction, Client, SplToken};
+//! use solana_sdk::{signature::Keypair, system_instruction::SystemInstruction};
+//!
+//! let client = Client::new(MockBackend::new(), Keypair::new());
+//! client.create_token_mint(&client.payer_pubkey(), 2).unwrap();
+//!
+//! let instructions = client.instructions();
+//! assert!(matches!(instructions[0].system(), Some(SystemInstruction::CreateAccount { .. })));
+//! assert!(matches!(instructions[1].token(), Some(TokenInstruction::InitializeMint { decimals: 2, .. })));
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use async_trait::async_trait;
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_address_lookup_table_program::instruction::ProgramInstruction;
+use solana_client::{
+    client_error::{self, ClientErrorKind},
+    rpc_response::RpcSimulateTransactionResult,
+};
+use solana_sdk::{
+    account::Account,
+    clock::Slot,
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::Signature,
+    system_instruction::SystemInstruction,
+    system_program,
+    transaction::{self, TransactionError, VersionedTransaction},
+};
+use spl_token::instruction::TokenInstruction;
+use spl_token_swap::instruction::SwapInstruction;
+
+use crate::{
+    backend::Backend,
+    error::is_swap_program,
+    nonblocking::backend::AsyncBackend,
+    receipt::{TransactionConfirmationStatus, TransactionDetails, TransactionStatus},
+    ClientError, ClientResult,
+};
+
+/// Block height the blockhashes of the mock stay valid until.
+const LAST_VALID_BLOCK_HEIGHT: u64 = 300;
+
+/// Backend recording every submitted transaction and answering with scripted accounts and results.
+///
+/// Transactions are not executed: they succeed unless a failure is scripted for them with
+/// [`MockBackend::fail_transaction`], and accounts only change when set with [`MockBackend::set_account`]. Every
+/// request of the latest blockhash returns a new one, as if a block was produced in between. Simulations return the
+/// outcome set with [`MockBackend::set_simulation`].
+#[derive(Debug)]
+pub struct MockBackend {
+    commitment: CommitmentConfig,
+    rent: Rent,
+    state: Mutex<MockState>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    accounts: HashMap<Pubkey, Account>,
+    account_errors: HashMap<Pubkey, String>,
+    transaction_errors: HashMap<usize, TransactionError>,
+    transaction_delays: HashMap<usize, usize>,
+    dropped_transactions: HashSet<usize>,
+    transaction_details: HashMap<usize, TransactionDetails>,
+    transactions: Vec<VersionedTransaction>,
+    statuses: HashMap<Signature, TransactionStatus>,
+    status_delays: HashMap<Signature, usize>,
+    details: HashMap<Signature, TransactionDetails>,
+    blockhashes: Vec<Hash>,
+    slot: Slot,
+    simulation: MockSimulation,
+}
+
+/// Scripted outcome of the simulated transactions.
+#[derive(Debug, Default)]
+struct MockSimulation {
+    logs: Vec<String>,
+    units_consumed: Option<u64>,
+    accounts: HashMap<Pubkey, Option<Account>>,
+}
+
+/// Instruction of a recorded transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedInstruction {
+    pub program_id: Pubkey,
+
+    /// Accounts of the instruction, those loaded from lookup tables are unknown to the mock and left default.
+    pub accounts: Vec<Pubkey>,
+    pub data: Vec<u8>,
+}
+
+impl RecordedInstruction {
+    pub fn system(&self) -> Option<SystemInstruction> {
+        if system_program::check_id(&self.program_id) {
+            bincode::deserialize(&self.data).ok()
+        } else {
+            None
+        }
+    }
+
+    pub fn token(&self) -> Option<TokenInstruction<'_>> {
+        if spl_token::check_id(&self.program_id) {
+            TokenInstruction::unpack(&self.data).ok()
+        } else {
+            None
+        }
+    }
+
+    pub fn lookup_table(&self) -> Option<ProgramInstruction> {
+        if solana_address_lookup_table_program::check_id(&self.program_id) {
+            bincode::deserialize(&self.data).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Decodes the instruction of the SPL token swap program, or of a deployment of it registered with
+    /// [`register_swap_program`](crate::register_swap_program).
+    pub fn swap(&self) -> Option<SwapInstruction> {
+        if is_swap_program(&self.program_id) {
+            SwapInstruction::unpack(&self.data).ok()
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for MockBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockBackend {
+    pub fn new() -> Self {
+        Self {
+            commitment: CommitmentConfig::confirmed(),
+            rent: Rent::default(),
+            state: Mutex::default(),
+        }
+    }
+
+    pub fn with_account(self, pubkey: Pubkey, account: Account) -> Self {
+        self.set_account(pubkey, account);
+        self
+    }
+
+    pub fn set_account(&self, pubkey: Pubkey, account: Account) {
+        self.state().accounts.insert(pubkey, account);
+    }
+
+    /// Makes every request of the account fail with the given message.
+    pub fn fail_account(&self, pubkey: Pubkey, message: impl Into<String>) {
+        self.state().account_errors.insert(pubkey, message.into());
+    }
+
+    /// Makes the transaction submitted with the given index, counting from zero, fail with the error.
+    pub fn fail_transaction(&self, index: usize, error: TransactionError) {
+        self.state().transaction_errors.insert(index, error);
+    }
+
+    /// Keeps the transaction submitted with the given index unknown for the given number of requests of its status,
+    /// as if it took that long to land.
+    pub fn delay_transaction(&self, index: usize, status_requests: usize) {
+        self.state().transaction_delays.insert(index, status_requests);
+    }
+
+    /// Makes the transaction submitted with the given index never land, its blockhash expiring right after it is sent.
+    pub fn drop_transaction(&self, index: usize) {
+        self.state().dropped_transactions.insert(index);
+    }
+
+    /// Sets the details served for the transaction submitted with the given index once it lands, none are served
+    /// otherwise.
+    pub fn set_transaction_details(&self, index: usize, details: TransactionDetails) {
+        self.state().transaction_details.insert(index, details);
+    }
+
+    /// Makes the simulated transactions return the logs and the consumed compute units, and leave the given accounts
+    /// in the given state, `None` meaning closed. The other accounts are returned unchanged.
+    pub fn set_simulation(
+        &self,
+        logs: Vec<String>,
+        units_consumed: u64,
+        accounts: impl IntoIterator<Item = (Pubkey, Option<Account>)>,
+    ) {
+        self.state().simulation = MockSimulation {
+            logs,
+            units_consumed: Some(units_consumed),
+            accounts: accounts.into_iter().collect(),
+        };
+    }
+
+    /// Returns the transactions submitted so far, both sent and simulated ones. Only the sent ones get a status.
+    pub fn transactions(&self) -> Vec<VersionedTransaction> {
+        self.state().transactions.clone()
+    }
+
+    /// Returns the instructions of all the submitted transactions in order.
+    pub fn instructions(&self) -> Vec<RecordedInstruction> {
+        self.state()
+            .transactions
+            .iter()
+            .flat_map(|transaction| {
+                let account_keys = transaction.message.static_account_keys();
+                let account = |index: &u8| account_keys.get(usize::from(*index)).copied().unwrap_or_default();
+                transaction
+                    .message
+                    .instructions()
+                    .iter()
+                    .map(|instruction| RecordedInstruction {
+                        program_id: *instruction.program_id(account_keys),
+                        accounts: instruction.accounts.iter().map(account).collect(),
+                        data: instruction.data.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Records the transaction and returns its index and scripted result.
+    fn submit(&self, transaction: &VersionedTransaction) -> (usize, transaction::Result<()>) {
+        let mut state = self.state();
+        let index = state.transactions.len();
+        state.transactions.push(transaction.clone());
+
+        (index, state.transaction_errors.remove(&index).map_or(Ok(()), Err))
+    }
+}
+
+impl Backend for MockBackend {
+    fn commitment(&self) -> CommitmentConfig {
+        self.commitment
+    }
+
+    fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> ClientResult<u64> {
+        Ok(self.rent.minimum_balance(data_len))
+    }
+
+    fn get_latest_blockhash_with_commitment(&self, _commitment: CommitmentConfig) -> ClientResult<(Hash, u64)> {
+        let blockhash = Hash::new_unique();
+        self.state().blockhashes.push(blockhash);
+        Ok((blockhash, LAST_VALID_BLOCK_HEIGHT))
+    }
+
+    fn is_blockhash_valid(&self, blockhash: &Hash, _commitment: CommitmentConfig) -> ClientResult<bool> {
+        Ok(self.state().blockhashes.contains(blockhash))
+    }
+
+    fn get_slot_with_commitment(&self, _commitment: CommitmentConfig) -> ClientResult<Slot> {
+        Ok(self.state().slot)
+    }
+
+    fn get_account_with_commitment(
+        &self,
+        pubkey: &Pubkey,
+        _commitment: CommitmentConfig,
+    ) -> ClientResult<Option<Account>> {
+        let state = self.state();
+        if let Some(message) = state.account_errors.get(pubkey) {
+            return Err(ClientError::Client(client_error::ClientError::from(
+                ClientErrorKind::Custom(message.clone()),
+            )));
+        }
+        Ok(state.accounts.get(pubkey).cloned())
+    }
+
+    fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        pubkeys
+            .iter()
+            .map(|pubkey| Backend::get_account_with_commitment(self, pubkey, self.commitment))
+            .collect()
+    }
+
+    fn get_recent_prioritization_fees(&self, _accounts: &[Pubkey]) -> ClientResult<Vec<u64>> {
+        Ok(Vec::new())
+    }
+
+    fn send_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        // The result is reported by the status, as if the preflight checks were skipped.
+        let (index, result) = self.submit(transaction);
+        let signature = transaction.signatures[0];
+        let mut state = self.state();
+        if state.dropped_transactions.remove(&index) {
+            let blockhash = transaction.message.recent_blockhash();
+            state.blockhashes.retain(|valid| valid != blockhash);
+            return Ok(signature);
+        }
+        if let Some(delay) = state.transaction_delays.remove(&index) {
+            state.status_delays.insert(signature, delay);
+        }
+        if let Some(details) = state.transaction_details.remove(&index) {
+            state.details.insert(signature, details);
+        }
+
+        let status = TransactionStatus {
+            slot: state.slot,
+            confirmations: None,
+            status: result.clone(),
+            err: result.err(),
+            confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+        };
+        state.statuses.insert(signature, status);
+        state.slot += 1;
+        Ok(signature)
+    }
+
+    fn resend_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        Ok(transaction.signatures[0])
+    }
+
+    fn get_signature_status_with_commitment(
+        &self,
+        signature: &Signature,
+        _commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionStatus>> {
+        let mut state = self.state();
+        if let Some(delay) = state.status_delays.get_mut(signature).filter(|delay| **delay > 0) {
+            *delay -= 1;
+            return Ok(None);
+        }
+        Ok(state.statuses.get(signature).cloned())
+    }
+
+    fn get_transaction_details(
+        &self,
+        signature: &Signature,
+        _commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionDetails>> {
+        Ok(self.state().details.get(signature).cloned())
+    }
+
+    fn simulate_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        accounts: &[Pubkey],
+    ) -> ClientResult<RpcSimulateTransactionResult> {
+        let (_, result) = self.submit(transaction);
+        let state = self.state();
+        let simulation = &state.simulation;
+        let accounts = accounts
+            .iter()
+            .map(|pubkey| {
+                let account = match simulation.accounts.get(pubkey) {
+                    Some(account) => account.as_ref(),
+                    None => state.accounts.get(pubkey),
+                };
+                account.map(|account| UiAccount::encode(pubkey, account, UiAccountEncoding::Base64, None, None))
+            })
+            .collect();
+
+        Ok(RpcSimulateTransactionResult {
+            err: result.err(),
+            logs: Some(simulation.logs.clone()),
+            accounts: Some(accounts),
+            units_consumed: simulation.units_consumed,
+            return_data: None,
+        })
+    }
+
+    fn airdrop(&self, to_pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature> {
+        self.state()
+            .accounts
+            .entry(*to_pubkey)
+            .or_insert_with(|| Account::new(0, 0, &system_program::id()))
+            .lamports += lamports;
+        Ok(Signature::new_unique())
+    }
+}
+
+#[async_trait]
+impl AsyncBackend for MockBackend {
+    fn commitment(&self) -> CommitmentConfig {
+        self.commitment
+    }
+
+    async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> ClientResult<u64> {
+        Backend::get_minimum_balance_for_rent_exemption(self, data_len)
+    }
+
+    async fn get_latest_blockhash_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<(Hash, u64)> {
+        Backend::get_latest_blockhash_with_commitment(self, commitment)
+    }
+
+    async fn is_blockhash_valid(&self, blockhash: &Hash, commitment: CommitmentConfig) -> ClientResult<bool> {
+        Backend::is_blockhash_valid(self, blockhash, commitment)
+    }
+
+    async fn get_slot_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<Slot> {
+        Backend::get_slot_with_commitment(self, commitment)
+    }
+
+    async fn get_account_with_commitment(
+        &self,
+        pubkey: &Pubkey,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<Account>> {
+        Backend::get_account_with_commitment(self, pubkey, commitment)
+    }
+
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        Backend::get_multiple_accounts(self, pubkeys)
+    }
+
+    async fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> ClientResult<Vec<u64>> {
+        Backend::get_recent_prioritization_fees(self, accounts)
+    }
+
+    async fn send_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        Backend::send_transaction(self, transaction)
+    }
+
+    async fn resend_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        Backend::resend_transaction(self, transaction)
+    }
+
+    async fn get_signature_status_with_commitment(
+        &self,
+        signature: &Signature,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionStatus>> {
+        Backend::get_signature_status_with_commitment(self, signature, commitment)
+    }
+
+    async fn get_transaction_details(
+        &self,
+        signature: &Signature,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionDetails>> {
+        Backend::get_transaction_details(self, signature, commitment)
+    }
+
+    async fn simulate_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        accounts: &[Pubkey],
+    ) -> ClientResult<RpcSimulateTransactionResult> {
+        Backend::simulate_transaction(self, transaction, accounts)
+    }
+
+    async fn airdrop(&self, to_pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature> {
+        Backend::airdrop(self, to_pubkey, lamports)
+    }
+}
@@ -0,0 +1,462 @@
+//! Token operations authorized by a multisig account, and transactions signed by its co-signers one by one.
+//!
+//! When the co-signers are not available at once, the transaction is passed between them as bytes, each of them
+//! adding their signature, until it can be sent:
+//!
+//! ```no_run
+//! use solana_client_helpers::{instructions, Client, PartialTransaction};
+//! use solana_sdk::signature::Signer;
+//! # use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+//! # let (client, multisig, token_mint, account): (Client, Pubkey, Pubkey, Pubkey) = unimplemented!();
+//! # let (first, second): (Keypair, Keypair) = unimplemented!();
+//!
+//! let signers = [&first.pubkey(), &second.pubkey()];
+//! let mint_to = instructions::token::mint_to(&multisig, &signers, &token_mint, &account, 100, 2)?;
+//! let transaction = client.partial_transaction(&mint_to.instructions, &[&first])?;
+//! let bytes = transaction.to_bytes();
+//!
+//! // elsewhere
+//! let mut transaction = PartialTransaction::from_bytes(&bytes)?;
+//! transaction.sign(&[&second])?;
+//! client.process_partial_transaction(transaction)?;
+//! # Ok::<(), solana_client_helpers::ClientError>(())
+//! ```
+//!
+//! The transaction expires with its blockhash, so co-signers taking longer than a minute or so need a client
+//! configured with a durable nonce.
+
+use solana_sdk::{
+    instruction::Instruction,
+    message::VersionedMessage,
+    pubkey::Pubkey,
+    sanitize::SanitizeError,
+    signature::{Signature, Signer},
+    signer::SignerError,
+    transaction::VersionedTransaction,
+};
+use tracing::instrument;
+
+use crate::{
+    backend::Backend, client::transaction_signers, instructions::token as instructions, nonce::DurableNonceConfig,
+    AuthorityType, Client, ClientError, ClientResult, TransactionReceipt,
+};
+
+/// Transaction collecting the signatures of its signers one by one, possibly on different machines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialTransaction {
+    transaction: VersionedTransaction,
+}
+
+impl PartialTransaction {
+    /// Creates an unsigned transaction of the message, which has to have its blockhash set.
+    pub fn new(message: VersionedMessage) -> Self {
+        let signatures = vec![Signature::default(); usize::from(message.header().num_required_signatures)];
+        Self {
+            transaction: VersionedTransaction { signatures, message },
+        }
+    }
+
+    /// Decodes a transaction passed by another co-signer, failing unless it is well-formed and has a signature slot
+    /// for every required signer.
+    pub fn from_bytes(bytes: &[u8]) -> ClientResult<Self> {
+        let transaction: VersionedTransaction = bincode::deserialize(bytes)?;
+        let num_required_signatures = usize::from(transaction.message.header().num_required_signatures);
+        if transaction.signatures.len() != num_required_signatures {
+            return Err(SanitizeError::InvalidValue.into());
+        }
+        transaction.sanitize()?;
+
+        Ok(Self { transaction })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(&self.transaction).expect("transaction is serializable")
+    }
+
+    pub fn message(&self) -> &VersionedMessage {
+        &self.transaction.message
+    }
+
+    /// Adds the signatures of the signers, failing if any of them isn't required by the transaction.
+    pub fn sign<S: Signer + ?Sized>(&mut self, signers: &[&S]) -> ClientResult<()> {
+        let message_data = self.transaction.message.serialize();
+        for signer in signers {
+            let pubkey = signer.try_pubkey()?;
+            let position = self
+                .required_signers()
+                .iter()
+                .position(|required| *required == pubkey)
+                .ok_or(SignerError::KeypairPubkeyMismatch)?;
+            self.transaction.signatures[position] = signer.try_sign_message(&message_data)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the pubkeys of the signers which haven't signed the transaction yet.
+    pub fn missing_signers(&self) -> Vec<Pubkey> {
+        self.required_signers()
+            .iter()
+            .zip(&self.transaction.signatures)
+            .filter(|(_, signature)| **signature == Signature::default())
+            .map(|(pubkey, _)| *pubkey)
+            .collect()
+    }
+
+    pub fn is_signed(&self) -> bool {
+        self.missing_signers().is_empty()
+    }
+
+    /// Returns the transaction once it is signed by all of its signers.
+    pub fn into_transaction(self) -> ClientResult<VersionedTransaction> {
+        let missing_signers = self.missing_signers();
+        if !missing_signers.is_empty() {
+            return Err(ClientError::MissingSignatures(missing_signers));
+        }
+
+        Ok(self.transaction)
+    }
+
+    fn required_signers(&self) -> &[Pubkey] {
+        let message = &self.transaction.message;
+        &message.static_account_keys()[..usize::from(message.header().num_required_signatures)]
+    }
+}
+
+/// Calls the builder with the pubkeys of the co-signers of a multisig authority.
+pub(crate) fn with_signer_pubkeys<S: Signer + ?Sized, T>(signers: &[&S], build: impl FnOnce(&[&Pubkey]) -> T) -> T {
+    let pubkeys = signers.iter().map(|signer| signer.pubkey()).collect::<Vec<_>>();
+    build(&pubkeys.iter().collect::<Vec<_>>())
+}
+
+/// Token operations of mints and token accounts whose authority is a multisig account, signed by enough of its
+/// co-signers at once.
+pub trait SplTokenMultisig {
+    fn mint_to_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt>;
+    #[allow(clippy::too_many_arguments)]
+    fn transfer_to_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        token_mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt>;
+    #[allow(clippy::too_many_arguments)]
+    fn approve_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        delegate: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt>;
+    fn revoke_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+    fn burn_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt>;
+    fn freeze_account_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+    fn thaw_account_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+    fn set_authority_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        owned: &Pubkey,
+        authority_type: AuthorityType,
+        new_authority: Option<&Pubkey>,
+    ) -> ClientResult<TransactionReceipt>;
+    fn close_token_account_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        account: &Pubkey,
+        destination: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+}
+
+impl<B: Backend> SplTokenMultisig for Client<B> {
+    #[instrument(skip_all, fields(multisig = %multisig, mint = %token_mint, account = %account, amount = amount))]
+    fn mint_to_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::mint_to(multisig, signer_pubkeys, token_mint, account, amount, decimals)
+        })?;
+        self.process_instructions(&instructions.instructions, signers)
+    }
+
+    #[instrument(
+        skip_all,
+        fields(multisig = %multisig, mint = %token_mint, source = %source, destination = %destination, amount = amount)
+    )]
+    fn transfer_to_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        token_mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::transfer_to(
+                multisig,
+                signer_pubkeys,
+                token_mint,
+                source,
+                destination,
+                amount,
+                decimals,
+            )
+        })?;
+        self.process_instructions(&instructions.instructions, signers)
+    }
+
+    #[instrument(
+        skip_all,
+        fields(multisig = %multisig, mint = %token_mint, account = %account, delegate = %delegate, amount = amount)
+    )]
+    fn approve_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        delegate: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::approve(
+                multisig,
+                signer_pubkeys,
+                token_mint,
+                account,
+                delegate,
+                amount,
+                decimals,
+            )
+        })?;
+        self.process_instructions(&instructions.instructions, signers)
+    }
+
+    #[instrument(skip_all, fields(multisig = %multisig, account = %account))]
+    fn revoke_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::revoke(multisig, signer_pubkeys, account)
+        })?;
+        self.process_instructions(&instructions.instructions, signers)
+    }
+
+    #[instrument(skip_all, fields(multisig = %multisig, mint = %token_mint, account = %account, amount = amount))]
+    fn burn_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::burn(multisig, signer_pubkeys, token_mint, account, amount, decimals)
+        })?;
+        self.process_instructions(&instructions.instructions, signers)
+    }
+
+    #[instrument(skip_all, fields(multisig = %multisig, mint = %token_mint, account = %account))]
+    fn freeze_account_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::freeze_account(multisig, signer_pubkeys, token_mint, account)
+        })?;
+        self.process_instructions(&instructions.instructions, signers)
+    }
+
+    #[instrument(skip_all, fields(multisig = %multisig, mint = %token_mint, account = %account))]
+    fn thaw_account_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::thaw_account(multisig, signer_pubkeys, token_mint, account)
+        })?;
+        self.process_instructions(&instructions.instructions, signers)
+    }
+
+    #[instrument(skip_all, fields(multisig = %multisig, owned = %owned, authority_type = ?authority_type))]
+    fn set_authority_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        owned: &Pubkey,
+        authority_type: AuthorityType,
+        new_authority: Option<&Pubkey>,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::set_authority(multisig, signer_pubkeys, owned, authority_type, new_authority)
+        })?;
+        self.process_instructions(&instructions.instructions, signers)
+    }
+
+    #[instrument(skip_all, fields(multisig = %multisig, account = %account, destination = %destination))]
+    fn close_token_account_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&dyn Signer],
+        account: &Pubkey,
+        destination: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::close_token_account(multisig, signer_pubkeys, account, destination)
+        })?;
+        self.process_instructions(&instructions.instructions, signers)
+    }
+}
+
+impl<B: Backend> Client<B> {
+    /// Builds a transaction of the instructions like [`Client::prepare_transaction`], but signs it only by the payer
+    /// and the given signers, leaving the rest of the signatures to be added later.
+    pub fn partial_transaction(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&dyn Signer],
+    ) -> ClientResult<PartialTransaction> {
+        let mut message = self.prepare_message(instructions, &self.compute_budget, &[])?;
+        message.set_recent_blockhash(self.message_blockhash(&message)?);
+
+        let nonce_authority = self.durable_nonce.as_ref().and_then(DurableNonceConfig::authority);
+        let signers = transaction_signers(&message, self.payer(), signers.iter().copied().chain(nonce_authority));
+        let mut transaction = PartialTransaction::new(message);
+        transaction.sign(&signers)?;
+        Ok(transaction)
+    }
+
+    /// Sends the transaction once it is signed by all of its signers. It is never re-signed, so it fails with
+    /// [`ClientError::Expired`] if its blockhash expires in the meantime.
+    pub fn process_partial_transaction(&self, transaction: PartialTransaction) -> ClientResult<TransactionReceipt> {
+        self.process_versioned_transaction(&transaction.into_transaction()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{hash::Hash, message::Message, signature::Keypair, system_instruction};
+
+    use super::*;
+
+    /// Transaction paid by the first signer, transferring lamports of the second one.
+    fn partial_transaction(first: &Keypair, second: &Keypair) -> PartialTransaction {
+        let transfer = system_instruction::transfer(&second.pubkey(), &Pubkey::new_unique(), 1);
+        let message = Message::new_with_blockhash(&[transfer], Some(&first.pubkey()), &Hash::new_unique());
+        PartialTransaction::new(VersionedMessage::Legacy(message))
+    }
+
+    #[test]
+    fn signatures_are_collected_one_by_one() {
+        let (first, second) = (Keypair::new(), Keypair::new());
+        let mut transaction = partial_transaction(&first, &second);
+        assert_eq!(transaction.missing_signers(), [first.pubkey(), second.pubkey()]);
+
+        transaction.sign(&[&second]).unwrap();
+        assert_eq!(transaction.missing_signers(), [first.pubkey()]);
+        assert!(matches!(
+            transaction.clone().into_transaction(),
+            Err(ClientError::MissingSignatures(missing)) if missing == [first.pubkey()]
+        ));
+
+        transaction.sign(&[&first]).unwrap();
+        assert!(transaction.is_signed());
+        let transaction = transaction.into_transaction().unwrap();
+        assert!(transaction.verify_with_results().into_iter().all(|verified| verified));
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let (first, second) = (Keypair::new(), Keypair::new());
+        let mut transaction = partial_transaction(&first, &second);
+        transaction.sign(&[&first]).unwrap();
+
+        let decoded = PartialTransaction::from_bytes(&transaction.to_bytes()).unwrap();
+        assert_eq!(decoded, transaction);
+        assert_eq!(decoded.missing_signers(), [second.pubkey()]);
+        assert!(PartialTransaction::from_bytes(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn malformed_bytes_are_rejected() {
+        let (first, second) = (Keypair::new(), Keypair::new());
+        let mut transaction = partial_transaction(&first, &second).transaction;
+        transaction.signatures.pop();
+        let bytes = bincode::serialize(&transaction).unwrap();
+
+        assert!(matches!(
+            PartialTransaction::from_bytes(&bytes),
+            Err(ClientError::InvalidTransaction(_))
+        ));
+    }
+
+    #[test]
+    fn unexpected_signer_is_rejected() {
+        let (first, second) = (Keypair::new(), Keypair::new());
+        let mut transaction = partial_transaction(&first, &second);
+
+        assert!(transaction.sign(&[&Keypair::new()]).is_err());
+        assert_eq!(transaction.missing_signers(), [first.pubkey(), second.pubkey()]);
+    }
+}
@@ -0,0 +1,14 @@
+//! Async counterparts of the helpers, built on top of the nonblocking RPC client.
+
+pub use crate::nonblocking::{
+    backend::*, client::*, confirmation::*, lookup_table::*, multisig::*, nonce::*, swap::*, token::*,
+};
+
+pub mod backend;
+pub mod client;
+pub mod confirmation;
+pub mod lookup_table;
+pub mod multisig;
+pub mod nonce;
+pub mod swap;
+pub mod token;
@@ -0,0 +1,183 @@
+use async_trait::async_trait;
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_response::RpcSimulateTransactionResult};
+use solana_sdk::{
+    account::Account, clock::Slot, commitment_config::CommitmentConfig, hash::Hash, message::VersionedMessage,
+    pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction,
+};
+
+use crate::{
+    error::decode_send_error,
+    receipt::{TransactionDetails, TransactionStatus},
+    retry::rebroadcast_config,
+    simulation::Simulation,
+    ClientResult,
+};
+
+/// Async counterpart of [`Backend`](crate::backend::Backend).
+#[async_trait]
+pub trait AsyncBackend: Send + Sync {
+    fn commitment(&self) -> CommitmentConfig;
+
+    async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> ClientResult<u64>;
+
+    /// Returns the latest blockhash together with the last block height it is valid at.
+    async fn get_latest_blockhash_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<(Hash, u64)>;
+
+    async fn is_blockhash_valid(&self, blockhash: &Hash, commitment: CommitmentConfig) -> ClientResult<bool>;
+
+    async fn get_slot_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<Slot>;
+
+    async fn get_account_with_commitment(
+        &self,
+        pubkey: &Pubkey,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<Account>>;
+
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> ClientResult<Vec<Option<Account>>>;
+
+    /// Returns the prioritization fees paid recently by transactions locking the given accounts.
+    async fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> ClientResult<Vec<u64>>;
+
+    /// Returns the fee the message is charged, `None` when the backend can't tell it.
+    async fn get_fee_for_message(&self, _message: &VersionedMessage) -> ClientResult<Option<u64>> {
+        Ok(None)
+    }
+
+    /// Submits the transaction without waiting for its confirmation, decoding the errors of its preflight checks.
+    async fn send_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature>;
+
+    /// Submits the already sent transaction once more, skipping the preflight checks.
+    async fn resend_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature>;
+
+    /// Returns the status of the transaction once it has reached the commitment, `None` until then.
+    async fn get_signature_status_with_commitment(
+        &self,
+        signature: &Signature,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionStatus>>;
+
+    /// Returns the fee, the consumed compute units and the logs of the processed transaction, `None` when the
+    /// backend doesn't serve them.
+    async fn get_transaction_details(
+        &self,
+        _signature: &Signature,
+        _commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionDetails>> {
+        Ok(None)
+    }
+
+    /// Simulates the transaction, returning the state of the given accounts after it when the backend supports it.
+    async fn simulate_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        accounts: &[Pubkey],
+    ) -> ClientResult<RpcSimulateTransactionResult>;
+
+    /// Funds the account and waits for the transfer to be confirmed.
+    async fn airdrop(&self, to_pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature>;
+}
+
+#[async_trait]
+impl AsyncBackend for RpcClient {
+    fn commitment(&self) -> CommitmentConfig {
+        RpcClient::commitment(self)
+    }
+
+    async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> ClientResult<u64> {
+        Ok(RpcClient::get_minimum_balance_for_rent_exemption(self, data_len).await?)
+    }
+
+    async fn get_latest_blockhash_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<(Hash, u64)> {
+        Ok(RpcClient::get_latest_blockhash_with_commitment(self, commitment).await?)
+    }
+
+    async fn is_blockhash_valid(&self, blockhash: &Hash, commitment: CommitmentConfig) -> ClientResult<bool> {
+        Ok(RpcClient::is_blockhash_valid(self, blockhash, commitment).await?)
+    }
+
+    async fn get_slot_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<Slot> {
+        Ok(RpcClient::get_slot_with_commitment(self, commitment).await?)
+    }
+
+    async fn get_account_with_commitment(
+        &self,
+        pubkey: &Pubkey,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<Account>> {
+        Ok(RpcClient::get_account_with_commitment(self, pubkey, commitment)
+            .await?
+            .value)
+    }
+
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        Ok(RpcClient::get_multiple_accounts(self, pubkeys).await?)
+    }
+
+    async fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> ClientResult<Vec<u64>> {
+        Ok(RpcClient::get_recent_prioritization_fees(self, accounts)
+            .await?
+            .into_iter()
+            .map(|fee| fee.prioritization_fee)
+            .collect())
+    }
+
+    async fn get_fee_for_message(&self, message: &VersionedMessage) -> ClientResult<Option<u64>> {
+        let fee = match message {
+            VersionedMessage::Legacy(message) => RpcClient::get_fee_for_message(self, message).await?,
+            VersionedMessage::V0(message) => RpcClient::get_fee_for_message(self, message).await?,
+        };
+        Ok(Some(fee))
+    }
+
+    async fn send_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        RpcClient::send_transaction(self, transaction)
+            .await
+            .map_err(|error| decode_send_error(error, transaction))
+    }
+
+    async fn resend_transaction(&self, transaction: &VersionedTransaction) -> ClientResult<Signature> {
+        Ok(RpcClient::send_transaction_with_config(self, transaction, rebroadcast_config()).await?)
+    }
+
+    async fn get_signature_status_with_commitment(
+        &self,
+        signature: &Signature,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionStatus>> {
+        let status = RpcClient::get_signature_statuses(self, &[*signature])
+            .await?
+            .value
+            .pop()
+            .flatten();
+        Ok(status.filter(|status| status.satisfies_commitment(commitment)))
+    }
+
+    async fn get_transaction_details(
+        &self,
+        signature: &Signature,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionDetails>> {
+        let transaction =
+            RpcClient::get_transaction_with_config(self, signature, TransactionDetails::config(commitment)).await?;
+        Ok(transaction.transaction.meta.map(TransactionDetails::from))
+    }
+
+    async fn simulate_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        accounts: &[Pubkey],
+    ) -> ClientResult<RpcSimulateTransactionResult> {
+        let config = Simulation::config(accounts, RpcClient::commitment(self));
+        Ok(RpcClient::simulate_transaction_with_config(self, transaction, config)
+            .await?
+            .value)
+    }
+
+    async fn airdrop(&self, to_pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature> {
+        let blockhash = RpcClient::get_latest_blockhash(self).await?;
+        let signature = RpcClient::request_airdrop_with_blockhash(self, to_pubkey, lamports, &blockhash).await?;
+        RpcClient::confirm_transaction_with_spinner(self, &signature, &blockhash, RpcClient::commitment(self)).await?;
+
+        Ok(signature)
+    }
+}
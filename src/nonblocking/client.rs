@@ -0,0 +1,724 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+    time::Instant,
+};
+
+pub use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    address_lookup_table_account::AddressLookupTableAccount,
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    instruction::Instruction,
+    message::VersionedMessage,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::{Transaction, VersionedTransaction},
+};
+
+use tokio::time::sleep;
+use tracing::{instrument, Instrument};
+
+use crate::{
+    amount::DecimalsCache,
+    blockhash::BlockhashCache,
+    client::{build_message, sign_message},
+    compute_budget::{fee_percentile, ComputeBudget, PriorityFee},
+    confirmation::{confirmed_status, Confirmation},
+    instructions,
+    nonblocking::{backend::AsyncBackend, confirmation::AsyncPendingTransaction, nonce::AsyncSystemNonce},
+    nonce::{nonce_account_of, DurableNonceConfig},
+    receipt::{ReceiptLog, TransactionReceipt, TransactionStatus},
+    retry::{RetryDecision, RetryPolicy, STATUS_POLL_INTERVAL},
+    simulation::{Simulation, SimulationLog},
+    telemetry::TransactionTelemetry,
+    ClientError, ClientResult,
+};
+
+/// Async client running the helpers against the backend, which is a nonblocking [`RpcClient`] by default.
+pub struct AsyncClient<B = RpcClient> {
+    pub client: B,
+    pub payer: Box<dyn Signer + Send + Sync>,
+    pub compute_budget: ComputeBudget,
+    pub simulation: Option<SimulationLog>,
+    pub retry_policy: RetryPolicy,
+    pub blockhash_cache: Option<Arc<BlockhashCache>>,
+    pub decimals_cache: Arc<DecimalsCache>,
+    pub durable_nonce: Option<DurableNonceConfig>,
+    pub confirmation: Confirmation,
+    pub receipt_details: bool,
+    pub receipt_log: Option<ReceiptLog>,
+}
+
+impl<B: AsyncBackend> AsyncClient<B> {
+    pub fn new(client: B, payer: impl Signer + Send + Sync + 'static) -> Self {
+        Self::with_boxed_payer(client, Box::new(payer))
+    }
+
+    pub(crate) fn with_boxed_payer(client: B, payer: Box<dyn Signer + Send + Sync>) -> Self {
+        Self {
+            client,
+            payer,
+            compute_budget: ComputeBudget::default(),
+            simulation: None,
+            retry_policy: RetryPolicy::default(),
+            blockhash_cache: None,
+            decimals_cache: Arc::default(),
+            durable_nonce: None,
+            confirmation: Confirmation::default(),
+            receipt_details: false,
+            receipt_log: None,
+        }
+    }
+
+    /// Sets the compute budget requested for every transaction built by the helpers.
+    pub fn with_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+
+    /// Sets the retry strategy applied to every transaction sent by the client.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Serves the latest blockhash from the given cache, which may be shared with another client connected to the
+    /// same cluster, saving a request per transaction.
+    ///
+    /// Identical transactions signed with the same blockhash have the same signature, so only the first of them lands
+    /// while the blockhash stays cached. Clients repeating transactions, e.g. the same transfer, shouldn't use it.
+    pub fn with_blockhash_cache(mut self, blockhash_cache: Arc<BlockhashCache>) -> Self {
+        self.blockhash_cache = Some(blockhash_cache);
+        self
+    }
+
+    /// Fetches a fresh blockhash for every transaction, which is the default.
+    pub fn without_blockhash_cache(mut self) -> Self {
+        self.blockhash_cache = None;
+        self
+    }
+
+    /// Shares the given cache of mint decimals, e.g. with another client connected to the same cluster.
+    pub fn with_decimals_cache(mut self, decimals_cache: Arc<DecimalsCache>) -> Self {
+        self.decimals_cache = decimals_cache;
+        self
+    }
+
+    /// Builds every transaction against the durable nonce instead of the latest blockhash.
+    pub fn with_durable_nonce(mut self, durable_nonce: DurableNonceConfig) -> Self {
+        self.durable_nonce = Some(durable_nonce);
+        self
+    }
+
+    /// Sets how long every transaction sent by the client is waited for, the commitment of the backend by default.
+    pub fn with_confirmation(mut self, confirmation: Confirmation) -> Self {
+        self.confirmation = confirmation;
+        self
+    }
+
+    /// Switches the client into the simulation mode, where transactions are simulated instead of being sent.
+    ///
+    /// Every simulation is recorded and can be inspected with [`AsyncClient::take_simulations`]. Note that state
+    /// changes of a simulated transaction are not visible to the following ones, so multi-transaction helpers like
+    /// `create_swap_and_init` are only validated up to the first transaction depending on a previous one.
+    pub fn with_simulation(mut self) -> Self {
+        self.simulation = Some(SimulationLog::default());
+        self
+    }
+
+    pub fn is_simulation(&self) -> bool {
+        self.simulation.is_some()
+    }
+
+    /// Returns the simulations recorded since the previous call.
+    pub fn take_simulations(&self) -> Vec<Simulation> {
+        self.simulation.as_ref().map(SimulationLog::take).unwrap_or_default()
+    }
+
+    /// Fetches the fee, the consumed compute units and the logs of every confirmed transaction into its receipt, at
+    /// the cost of another request per transaction.
+    pub fn with_receipt_details(mut self) -> Self {
+        self.receipt_details = true;
+        self
+    }
+
+    /// Records the receipt of every transaction sent by the client, to be collected with
+    /// [`AsyncClient::take_receipts`].
+    pub fn with_receipt_log(mut self) -> Self {
+        self.receipt_log = Some(ReceiptLog::default());
+        self
+    }
+
+    /// Returns the receipts recorded since the previous call.
+    pub fn take_receipts(&self) -> Vec<TransactionReceipt> {
+        self.receipt_log.as_ref().map(ReceiptLog::take).unwrap_or_default()
+    }
+
+    pub fn payer(&self) -> &(dyn Signer + Send + Sync) {
+        self.payer.as_ref()
+    }
+
+    pub fn payer_pubkey(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// Returns the latest blockhash, served from the cache, if any, until it gets close to expiry.
+    pub async fn latest_blockhash(&self) -> ClientResult<Hash> {
+        if let Some(blockhash) = self.blockhash_cache.as_ref().and_then(|cache| cache.get()) {
+            return Ok(blockhash);
+        }
+
+        let (blockhash, last_valid_block_height) = self.get_latest_blockhash_with_commitment(self.commitment()).await?;
+        if let Some(cache) = &self.blockhash_cache {
+            cache.update(blockhash, last_valid_block_height);
+        }
+
+        Ok(blockhash)
+    }
+
+    pub async fn process_transaction(&self, transaction: &Transaction) -> ClientResult<TransactionReceipt> {
+        self.process_versioned_transaction(&transaction.clone().into()).await
+    }
+
+    pub async fn process_versioned_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> ClientResult<TransactionReceipt> {
+        self.process_transaction_with_signers(transaction, None, self.confirmation)
+            .await
+    }
+
+    /// Same as [`AsyncClient::process_versioned_transaction`], but overrides the confirmation of the client.
+    pub async fn process_transaction_with_confirmation(
+        &self,
+        transaction: &VersionedTransaction,
+        confirmation: Confirmation,
+    ) -> ClientResult<TransactionReceipt> {
+        self.process_transaction_with_signers(transaction, None, confirmation)
+            .await
+    }
+
+    /// Sends the transaction according to the retry policy, re-signing it by the payer and the given signers when
+    /// its blockhash expires, and waits for it as long as the confirmation requires.
+    pub(crate) async fn process_transaction_with_signers(
+        &self,
+        transaction: &VersionedTransaction,
+        signers: Option<&[&(dyn Signer + Sync)]>,
+        confirmation: Confirmation,
+    ) -> ClientResult<TransactionReceipt> {
+        if let Some(log) = &self.simulation {
+            let simulation = self.simulate(transaction).await?;
+            return log
+                .record_result(simulation)
+                .map(|receipt| self.record_receipt(receipt));
+        }
+
+        let mut telemetry = TransactionTelemetry::start(transaction);
+        let span = telemetry.span().clone();
+
+        let mut transaction = transaction.clone();
+        let commitment = confirmation.commitment(self.commitment());
+        let result = match self
+            .send_with_retries(&mut transaction, signers, commitment, &mut telemetry)
+            .instrument(span.clone())
+            .await
+        {
+            Ok(status) => Ok(self
+                .receipt(&transaction, status.as_ref())
+                .instrument(span)
+                .await),
+            Err(error) => Err(error),
+        };
+        let fee = result.as_ref().ok().and_then(|receipt| receipt.fee);
+        telemetry.finish(&result, fee);
+        result.map(|receipt| self.record_receipt(receipt))
+    }
+
+    /// Sends the transaction according to the retry policy like [`AsyncClient::process_versioned_transaction`], but
+    /// returns without waiting for its confirmation, which is left to the returned handle.
+    pub async fn submit_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> ClientResult<AsyncPendingTransaction<'_, B>> {
+        self.submit_transaction_with_signers(transaction, None).await
+    }
+
+    pub(crate) async fn submit_transaction_with_signers(
+        &self,
+        transaction: &VersionedTransaction,
+        signers: Option<&[&(dyn Signer + Sync)]>,
+    ) -> ClientResult<AsyncPendingTransaction<'_, B>> {
+        if self.is_simulation() {
+            let receipt = self
+                .process_transaction_with_signers(transaction, signers, Confirmation::Sent)
+                .await?;
+            return Ok(AsyncPendingTransaction::resolved(self, transaction.clone(), receipt));
+        }
+
+        let mut telemetry = TransactionTelemetry::start(transaction);
+        let span = telemetry.span().clone();
+
+        let mut transaction = transaction.clone();
+        match self
+            .send_with_retries(&mut transaction, signers, None, &mut telemetry)
+            .instrument(span)
+            .await
+        {
+            Ok(_) => Ok(AsyncPendingTransaction::new(
+                self,
+                transaction,
+                self.commitment(),
+                telemetry,
+            )),
+            Err(error) => {
+                let result = Err(error);
+                telemetry.finish(&result, None);
+                result
+            },
+        }
+    }
+
+    /// Makes the receipt of the sent transaction, fetching the details of a confirmed one if the client is
+    /// configured so.
+    pub(crate) async fn receipt(
+        &self,
+        transaction: &VersionedTransaction,
+        status: Option<&TransactionStatus>,
+    ) -> TransactionReceipt {
+        let signature = transaction.signatures[0];
+        let details = match status {
+            Some(_) if self.receipt_details => self
+                .get_transaction_details(&signature, self.commitment())
+                .await
+                .ok()
+                .flatten(),
+            _ => None,
+        };
+        TransactionReceipt::assemble(signature, status, details)
+    }
+
+    pub(crate) fn record_receipt(&self, receipt: TransactionReceipt) -> TransactionReceipt {
+        if let Some(log) = &self.receipt_log {
+            log.record(receipt.clone());
+        }
+        receipt
+    }
+
+    async fn send_with_retries(
+        &self,
+        transaction: &mut VersionedTransaction,
+        signers: Option<&[&(dyn Signer + Sync)]>,
+        commitment: Option<CommitmentConfig>,
+        telemetry: &mut TransactionTelemetry,
+    ) -> ClientResult<Option<TransactionStatus>> {
+        let mut retry = 0;
+        loop {
+            let error = match self.send_and_confirm_attempt(transaction, retry > 0, commitment).await {
+                Ok(status) => return Ok(status),
+                Err(error) => error,
+            };
+
+            retry += 1;
+            match (self.retry_policy.decide(retry, &error, signers.is_some()), signers) {
+                (RetryDecision::Retry, _) => {},
+                (RetryDecision::Resign, Some(signers)) => {
+                    if let Some(cache) = &self.blockhash_cache {
+                        cache.invalidate();
+                    }
+                    self.sign_transaction(transaction, signers).await?;
+                },
+                _ => return Err(error),
+            }
+            sleep(self.retry_policy.backoff(retry)).await;
+            telemetry.retried(transaction);
+        }
+    }
+
+    /// Sends the transaction unless it has already landed, then waits for it to reach the commitment, rebroadcasting
+    /// it until the blockhash expires. Returns `None` right after sending when there is no commitment to wait for.
+    async fn send_and_confirm_attempt(
+        &self,
+        transaction: &VersionedTransaction,
+        check_status: bool,
+        commitment: Option<CommitmentConfig>,
+    ) -> ClientResult<Option<TransactionStatus>> {
+        let signature = transaction.signatures[0];
+        let landed = check_status
+            && self
+                .get_signature_status_with_commitment(&signature, CommitmentConfig::processed())
+                .await?
+                .is_some();
+        if !landed {
+            self.send_transaction(transaction).await?;
+        }
+        let commitment = match commitment {
+            Some(commitment) => commitment,
+            None => return Ok(None),
+        };
+
+        let mut broadcast_at = Instant::now();
+        loop {
+            if let Some(status) = self.check_confirmation(transaction, commitment).await? {
+                return Ok(Some(status));
+            }
+            if broadcast_at.elapsed() >= self.retry_policy.rebroadcast_interval {
+                self.resend_transaction(transaction).await?;
+                broadcast_at = Instant::now();
+            }
+            sleep(STATUS_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Returns the status of the sent transaction once it has reached the commitment, failing when the transaction
+    /// has failed or can't land anymore.
+    pub(crate) async fn check_confirmation(
+        &self,
+        transaction: &VersionedTransaction,
+        commitment: CommitmentConfig,
+    ) -> ClientResult<Option<TransactionStatus>> {
+        let signature = transaction.signatures[0];
+        if let Some(status) = self
+            .get_signature_status_with_commitment(&signature, commitment)
+            .await?
+        {
+            return confirmed_status(transaction, status).map(Some);
+        }
+        if self.is_blockhash_expired(transaction).await?
+            && self
+                .get_signature_status_with_commitment(&signature, CommitmentConfig::processed())
+                .await?
+                .is_none()
+        {
+            return Err(ClientError::Expired(signature));
+        }
+        Ok(None)
+    }
+
+    /// Whether the blockhash of the transaction can't be used anymore, i.e. it is too old or the durable nonce has
+    /// been advanced.
+    async fn is_blockhash_expired(&self, transaction: &VersionedTransaction) -> ClientResult<bool> {
+        let blockhash = transaction.message.recent_blockhash();
+        Ok(match nonce_account_of(&transaction.message) {
+            Some(nonce_account) => self.get_nonce_blockhash(&nonce_account).await? != *blockhash,
+            None => {
+                !self
+                    .is_blockhash_valid(blockhash, CommitmentConfig::processed())
+                    .await?
+            },
+        })
+    }
+
+    /// Simulates the transaction against the current state, collecting the changes of its writable accounts.
+    pub async fn simulate(&self, transaction: &VersionedTransaction) -> ClientResult<Simulation> {
+        let accounts = Simulation::writable_accounts(transaction);
+        let accounts_before = self.get_multiple_accounts(&accounts).await?;
+        let result = self.simulate_transaction(transaction, &accounts).await?;
+
+        Ok(Simulation::new(transaction.clone(), result, &accounts, accounts_before))
+    }
+
+    /// Signs the instructions by the payer and the given signers, then sends them in a single transaction.
+    pub async fn process_instructions(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&(dyn Signer + Sync)],
+    ) -> ClientResult<TransactionReceipt> {
+        self.process_instructions_with_compute_budget(instructions, signers, &self.compute_budget)
+            .await
+    }
+
+    /// Same as [`AsyncClient::process_instructions`], but overrides the compute budget of the client.
+    pub async fn process_instructions_with_compute_budget(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&(dyn Signer + Sync)],
+        compute_budget: &ComputeBudget,
+    ) -> ClientResult<TransactionReceipt> {
+        let transaction = self
+            .prepare_transaction(instructions, signers, compute_budget, &[])
+            .await?;
+        self.process_transaction_with_signers(&transaction, Some(signers), self.confirmation)
+            .await
+    }
+
+    /// Same as [`AsyncClient::process_instructions`], but sends a versioned transaction compiled against the given
+    /// address lookup tables, so that it can reference more accounts than a legacy one.
+    pub async fn process_instructions_with_lookup_tables(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&(dyn Signer + Sync)],
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> ClientResult<TransactionReceipt> {
+        let transaction = self
+            .prepare_transaction(instructions, signers, &self.compute_budget, lookup_tables)
+            .await?;
+        self.process_transaction_with_signers(&transaction, Some(signers), self.confirmation)
+            .await
+    }
+
+    /// Same as [`AsyncClient::process_instructions`], but overrides the confirmation of the client.
+    pub async fn process_instructions_with_confirmation(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&(dyn Signer + Sync)],
+        confirmation: Confirmation,
+    ) -> ClientResult<TransactionReceipt> {
+        let transaction = self
+            .prepare_transaction(instructions, signers, &self.compute_budget, &[])
+            .await?;
+        self.process_transaction_with_signers(&transaction, Some(signers), confirmation)
+            .await
+    }
+
+    /// Same as [`AsyncClient::process_instructions`], but returns right after sending the transaction, leaving its
+    /// confirmation to the returned handle.
+    pub async fn submit_instructions(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&(dyn Signer + Sync)],
+    ) -> ClientResult<AsyncPendingTransaction<'_, B>> {
+        let transaction = self
+            .prepare_transaction(instructions, signers, &self.compute_budget, &[])
+            .await?;
+        self.submit_transaction_with_signers(&transaction, Some(signers)).await
+    }
+
+    /// Builds a transaction of the given instructions, applying the compute budget and the durable nonce of the
+    /// client, and signs it by the payer and those of the given signers which are required.
+    ///
+    /// The transaction is a legacy one unless lookup tables are given, then a v0 message is compiled against them.
+    pub async fn prepare_transaction(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&(dyn Signer + Sync)],
+        compute_budget: &ComputeBudget,
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> ClientResult<VersionedTransaction> {
+        let mut transaction = VersionedTransaction {
+            signatures: Vec::new(),
+            message: self
+                .prepare_message(instructions, compute_budget, lookup_tables)
+                .await?,
+        };
+        self.sign_transaction(&mut transaction, signers).await?;
+        Ok(transaction)
+    }
+
+    /// Compiles the message of [`AsyncClient::prepare_transaction`], leaving its blockhash unset.
+    pub(crate) async fn prepare_message(
+        &self,
+        instructions: &[Instruction],
+        compute_budget: &ComputeBudget,
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> ClientResult<VersionedMessage> {
+        let unit_price = self.compute_unit_price(compute_budget, instructions).await?;
+        build_message(
+            instructions,
+            &self.payer_pubkey(),
+            compute_budget,
+            unit_price,
+            self.durable_nonce.as_ref(),
+            lookup_tables,
+        )
+    }
+
+    /// Returns the blockhash to sign the message with, the one of its durable nonce or the latest one.
+    pub(crate) async fn message_blockhash(&self, message: &VersionedMessage) -> ClientResult<Hash> {
+        match nonce_account_of(message) {
+            Some(nonce_account) => self.get_nonce_blockhash(&nonce_account).await,
+            None => self.latest_blockhash().await,
+        }
+    }
+
+    /// Signs the transaction with the latest blockhash, or the blockhash of its durable nonce.
+    pub async fn sign_transaction(
+        &self,
+        transaction: &mut VersionedTransaction,
+        signers: &[&(dyn Signer + Sync)],
+    ) -> ClientResult<()> {
+        let blockhash = self.message_blockhash(&transaction.message).await?;
+        let nonce_authority = self.durable_nonce.as_ref().and_then(DurableNonceConfig::authority);
+        *transaction = sign_message(
+            &transaction.message,
+            blockhash,
+            self.payer(),
+            signers
+                .iter()
+                .map(|signer| *signer as &dyn Signer)
+                .chain(nonce_authority),
+        )?;
+        Ok(())
+    }
+
+    /// Resolves the price per compute unit for the given instructions according to the priority fee policy.
+    pub async fn compute_unit_price(
+        &self,
+        compute_budget: &ComputeBudget,
+        instructions: &[Instruction],
+    ) -> ClientResult<Option<u64>> {
+        Ok(match compute_budget.priority_fee {
+            PriorityFee::None => None,
+            PriorityFee::Fixed(unit_price) => Some(unit_price),
+            PriorityFee::Percentile { percentile, max } => {
+                let fees = self
+                    .get_recent_prioritization_fees(&ComputeBudget::fee_accounts(instructions))
+                    .await?;
+                let unit_price = fee_percentile(fees, percentile);
+                Some(max.map_or(unit_price, |max| unit_price.min(max)))
+            },
+        })
+    }
+
+    #[instrument(skip_all, fields(owner = %owner, data_len = account_data_len))]
+    pub async fn create_account(
+        &self,
+        owner: &Pubkey,
+        account_data_len: usize,
+        lamports: Option<u64>,
+    ) -> ClientResult<Keypair> {
+        let account = Keypair::new();
+        let lamports = if let Some(lamports) = lamports {
+            lamports
+        } else {
+            self.get_minimum_balance_for_rent_exemption(account_data_len).await?
+        };
+
+        let instructions = instructions::system::create_account(
+            &self.payer_pubkey(),
+            &account.pubkey(),
+            owner,
+            account_data_len,
+            lamports,
+        );
+        self.process_instructions(&instructions.instructions, &[&account])
+            .await?;
+
+        Ok(account)
+    }
+
+    #[instrument(skip_all, fields(to = %to_pubkey, lamports = lamports))]
+    pub async fn airdrop(&self, to_pubkey: &Pubkey, lamports: u64) -> ClientResult<Signature> {
+        self.client.airdrop(to_pubkey, lamports).await
+    }
+}
+
+impl<B> Deref for AsyncClient<B> {
+    type Target = B;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+impl<B> DerefMut for AsyncClient<B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.client
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, time::Duration};
+
+    use futures::future::try_join_all;
+    use solana_sdk::system_instruction::SystemInstruction;
+    use spl_token::instruction::TokenInstruction;
+
+    use super::*;
+    use crate::{
+        mock::{MockBackend, RecordedInstruction},
+        nonblocking::token::AsyncSplToken,
+    };
+
+    #[tokio::test]
+    async fn token_mint_is_created() {
+        let client = AsyncClient::new(MockBackend::new(), Keypair::new());
+        let owner = Pubkey::new_unique();
+
+        let token_mint = client.create_token_mint(&owner, 6).await.unwrap();
+
+        let instructions = client.instructions();
+        assert_eq!(instructions.len(), 2);
+        let create_account = instructions[0].system();
+        assert!(matches!(create_account, Some(SystemInstruction::CreateAccount { .. })));
+        assert_eq!(instructions[0].accounts[1], token_mint.pubkey());
+        assert!(matches!(
+            instructions[1].token(),
+            Some(TokenInstruction::InitializeMint { decimals: 6, mint_authority, .. }) if mint_authority == owner
+        ));
+        assert_eq!(client.transactions().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn expired_transaction_is_resigned() {
+        let retry_policy = RetryPolicy::default()
+            .with_max_attempts(2)
+            .with_backoff(Duration::ZERO, Duration::ZERO)
+            .with_resign_on_expiry(true);
+        let client = AsyncClient::new(MockBackend::new(), Keypair::new()).with_retry_policy(retry_policy);
+        client.drop_transaction(0);
+        let (owner, mint) = (Keypair::new(), Pubkey::new_unique());
+
+        let receipt = client
+            .transfer_to(&owner, &mint, &Pubkey::new_unique(), &Pubkey::new_unique(), 40, 6)
+            .await
+            .unwrap();
+
+        let transactions = client.transactions();
+        assert_eq!(transactions.len(), 2);
+        let blockhashes: Vec<_> = transactions
+            .iter()
+            .map(|transaction| transaction.message.recent_blockhash())
+            .collect();
+        assert_ne!(blockhashes[0], blockhashes[1]);
+        assert_eq!(receipt.signature, transactions[1].signatures[0]);
+        assert!(receipt.slot.is_some());
+    }
+
+    #[tokio::test]
+    async fn expired_transaction_fails_without_resigning() {
+        let client = AsyncClient::new(MockBackend::new(), Keypair::new());
+        client.drop_transaction(0);
+        let (owner, mint) = (Keypair::new(), Pubkey::new_unique());
+
+        let result = client
+            .transfer_to(&owner, &mint, &Pubkey::new_unique(), &Pubkey::new_unique(), 40, 6)
+            .await;
+
+        assert!(matches!(result, Err(ClientError::Expired(_))));
+        assert_eq!(client.transactions().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn batch_is_sent_concurrently() {
+        let client = AsyncClient::new(MockBackend::new(), Keypair::new());
+        let (owner, mint, source) = (Keypair::new(), Pubkey::new_unique(), Pubkey::new_unique());
+        let recipients: Vec<_> = (0..3).map(|_| Pubkey::new_unique()).collect();
+
+        let transfers = recipients
+            .iter()
+            .map(|recipient| client.transfer_to(&owner, &mint, &source, recipient, 10, 6));
+        let receipts = try_join_all(transfers).await.unwrap();
+
+        let sent: HashSet<_> = client
+            .transactions()
+            .iter()
+            .map(|transaction| transaction.signatures[0])
+            .collect();
+        let landed: HashSet<_> = receipts.iter().map(|receipt| receipt.signature).collect();
+        assert_eq!(sent.len(), 3);
+        assert_eq!(sent, landed);
+
+        let instructions = client.instructions();
+        let transfer = |instruction: &RecordedInstruction| {
+            matches!(
+                instruction.token(),
+                Some(TokenInstruction::TransferChecked { amount: 10, .. })
+            )
+        };
+        assert!(instructions.iter().all(transfer));
+        let destinations: HashSet<_> = instructions.iter().map(|instruction| instruction.accounts[2]).collect();
+        assert_eq!(destinations, recipients.into_iter().collect());
+    }
+}
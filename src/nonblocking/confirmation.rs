@@ -0,0 +1,222 @@
+use std::time::Instant;
+
+use solana_sdk::{commitment_config::CommitmentConfig, signature::Signature, transaction::VersionedTransaction};
+use tokio::time::sleep;
+use tracing::{Instrument, Span};
+
+use crate::{
+    nonblocking::{backend::AsyncBackend, AsyncClient, RpcClient},
+    receipt::TransactionReceipt,
+    retry::{is_transient, STATUS_POLL_INTERVAL},
+    telemetry::TransactionTelemetry,
+    ClientResult,
+};
+
+/// Transaction sent by [`AsyncClient::submit_transaction`], which can be polled or awaited until it is confirmed.
+///
+/// The transaction is rebroadcast while it is polled, but never re-signed, so it fails with
+/// [`ClientError::Expired`](crate::ClientError::Expired) once its blockhash expires.
+pub struct AsyncPendingTransaction<'a, B = RpcClient> {
+    client: &'a AsyncClient<B>,
+    transaction: VersionedTransaction,
+    commitment: CommitmentConfig,
+    broadcast_at: Instant,
+    telemetry: Option<TransactionTelemetry>,
+    receipt: Option<TransactionReceipt>,
+}
+
+impl<'a, B: AsyncBackend> AsyncPendingTransaction<'a, B> {
+    pub(crate) fn new(
+        client: &'a AsyncClient<B>,
+        transaction: VersionedTransaction,
+        commitment: CommitmentConfig,
+        telemetry: TransactionTelemetry,
+    ) -> Self {
+        Self {
+            client,
+            transaction,
+            commitment,
+            broadcast_at: Instant::now(),
+            telemetry: Some(telemetry),
+            receipt: None,
+        }
+    }
+
+    /// Creates a handle of a transaction which doesn't have to be waited for, e.g. a simulated one.
+    pub(crate) fn resolved(
+        client: &'a AsyncClient<B>,
+        transaction: VersionedTransaction,
+        receipt: TransactionReceipt,
+    ) -> Self {
+        Self {
+            client,
+            transaction,
+            commitment: client.commitment(),
+            broadcast_at: Instant::now(),
+            telemetry: None,
+            receipt: Some(receipt),
+        }
+    }
+
+    /// Sets the commitment the transaction is waited for, the one of the backend by default.
+    pub fn with_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    pub fn signature(&self) -> Signature {
+        self.transaction.signatures[0]
+    }
+
+    pub fn transaction(&self) -> &VersionedTransaction {
+        &self.transaction
+    }
+
+    /// Checks the status of the transaction once, rebroadcasting it when it is due. Returns the receipt once the
+    /// transaction is confirmed, `None` until then.
+    pub async fn poll(&mut self) -> ClientResult<Option<TransactionReceipt>> {
+        if let Some(receipt) = &self.receipt {
+            return Ok(Some(receipt.clone()));
+        }
+
+        let span = self
+            .telemetry
+            .as_ref()
+            .map_or_else(Span::none, |telemetry| telemetry.span().clone());
+        let status = self
+            .client
+            .check_confirmation(&self.transaction, self.commitment)
+            .instrument(span.clone())
+            .await;
+
+        match status {
+            Ok(Some(status)) => {
+                let receipt = self
+                    .client
+                    .receipt(&self.transaction, Some(&status))
+                    .instrument(span)
+                    .await;
+                self.finish(Ok(receipt))
+            },
+            Ok(None) => {
+                if self.broadcast_at.elapsed() >= self.client.retry_policy.rebroadcast_interval {
+                    self.client
+                        .resend_transaction(&self.transaction)
+                        .instrument(span)
+                        .await?;
+                    self.broadcast_at = Instant::now();
+                }
+                Ok(None)
+            },
+            Err(error) if is_transient(&error) => Err(error),
+            Err(error) => self.finish(Err(error)),
+        }
+    }
+
+    /// Waits until the transaction is confirmed.
+    pub async fn confirm(mut self) -> ClientResult<TransactionReceipt> {
+        loop {
+            if let Some(receipt) = self.poll().await? {
+                return Ok(receipt);
+            }
+            sleep(STATUS_POLL_INTERVAL).await;
+        }
+    }
+
+    fn finish(&mut self, result: ClientResult<TransactionReceipt>) -> ClientResult<Option<TransactionReceipt>> {
+        if let Some(telemetry) = self.telemetry.take() {
+            let fee = result.as_ref().ok().and_then(|receipt| receipt.fee);
+            telemetry.finish(&result, fee);
+        }
+
+        let receipt = self.client.record_receipt(result?);
+        self.receipt = Some(receipt.clone());
+        Ok(Some(receipt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    };
+    use spl_token::error::TokenError;
+
+    use super::*;
+    use crate::{confirmation::Confirmation, error::SplError, mock::MockBackend, ClientError};
+
+    fn token_transfer(client: &AsyncClient<MockBackend>) -> Instruction {
+        let (source, destination) = (Pubkey::new_unique(), Pubkey::new_unique());
+        spl_token::instruction::transfer(&spl_token::id(), &source, &destination, &client.payer_pubkey(), &[], 40)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn sent_transaction_is_not_waited_for() {
+        let client = AsyncClient::new(MockBackend::new(), Keypair::new());
+        client.delay_transaction(0, usize::MAX);
+
+        let receipt = client
+            .process_instructions_with_confirmation(&[token_transfer(&client)], &[], Confirmation::Sent)
+            .await
+            .unwrap();
+
+        assert_eq!(receipt.signature, client.transactions()[0].signatures[0]);
+        assert_eq!(receipt.slot, None);
+        assert_eq!(receipt.confirmation_status, None);
+    }
+
+    #[tokio::test]
+    async fn submitted_transaction_lands_when_polled() {
+        let client = AsyncClient::new(MockBackend::new(), Keypair::new());
+        client.delay_transaction(0, 1);
+
+        let mut pending = client
+            .submit_instructions(&[token_transfer(&client)], &[])
+            .await
+            .unwrap();
+        assert!(pending.poll().await.unwrap().is_none());
+
+        let receipt = pending.poll().await.unwrap().unwrap();
+        assert_eq!(receipt.signature, pending.signature());
+        assert_eq!(receipt.slot, Some(0));
+        assert!(receipt.confirmation_status.is_some());
+    }
+
+    #[tokio::test]
+    async fn dropped_transaction_expires() {
+        let client = AsyncClient::new(MockBackend::new(), Keypair::new());
+        client.drop_transaction(0);
+
+        let pending = client
+            .submit_instructions(&[token_transfer(&client)], &[])
+            .await
+            .unwrap();
+        let signature = pending.signature();
+
+        assert!(matches!(pending.confirm().await, Err(ClientError::Expired(expired)) if expired == signature));
+    }
+
+    #[tokio::test]
+    async fn failed_transaction_is_decoded() {
+        let client = AsyncClient::new(MockBackend::new(), Keypair::new());
+        client.fail_transaction(0, TransactionError::InstructionError(0, InstructionError::Custom(1)));
+
+        let pending = client
+            .submit_instructions(&[token_transfer(&client)], &[])
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            pending.confirm().await,
+            Err(ClientError::Instruction {
+                index: 0,
+                error: SplError::Token(TokenError::InsufficientFunds),
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use solana_sdk::{
+    address_lookup_table_account::AddressLookupTableAccount, commitment_config::CommitmentConfig, pubkey::Pubkey,
+    signature::Signer,
+};
+
+use crate::{
+    instructions::lookup_table::{self as instructions, MAX_EXTEND_ADDRESSES},
+    lookup_table::lookup_table_account,
+    nonblocking::{backend::AsyncBackend, AsyncClient},
+    ClientError, ClientResult, TransactionReceipt,
+};
+
+#[async_trait]
+pub trait AsyncAddressLookupTables {
+    async fn create_lookup_table(&self, authority: &Pubkey) -> ClientResult<Pubkey>;
+    async fn get_lookup_table(&self, lookup_table: &Pubkey) -> ClientResult<AddressLookupTableAccount>;
+    async fn extend_lookup_table(
+        &self,
+        lookup_table: &Pubkey,
+        authority: &(dyn Signer + Sync),
+        addresses: &[Pubkey],
+    ) -> ClientResult<Vec<TransactionReceipt>>;
+    async fn deactivate_lookup_table(
+        &self,
+        lookup_table: &Pubkey,
+        authority: &(dyn Signer + Sync),
+    ) -> ClientResult<TransactionReceipt>;
+    async fn close_lookup_table(
+        &self,
+        lookup_table: &Pubkey,
+        authority: &(dyn Signer + Sync),
+        recipient: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+}
+
+#[async_trait]
+impl<B: AsyncBackend> AsyncAddressLookupTables for AsyncClient<B> {
+    async fn create_lookup_table(&self, authority: &Pubkey) -> ClientResult<Pubkey> {
+        let recent_slot = self.get_slot_with_commitment(CommitmentConfig::finalized()).await?;
+        let (instructions, lookup_table) =
+            instructions::create_lookup_table(authority, &self.payer_pubkey(), recent_slot);
+        self.process_instructions(&instructions.instructions, &[]).await?;
+
+        Ok(lookup_table)
+    }
+
+    async fn get_lookup_table(&self, lookup_table: &Pubkey) -> ClientResult<AddressLookupTableAccount> {
+        let account = self
+            .get_account_with_commitment(lookup_table, self.commitment())
+            .await?
+            .ok_or(ClientError::AccountNotFound(*lookup_table))?;
+        lookup_table_account(lookup_table, &account)
+    }
+
+    async fn extend_lookup_table(
+        &self,
+        lookup_table: &Pubkey,
+        authority: &(dyn Signer + Sync),
+        addresses: &[Pubkey],
+    ) -> ClientResult<Vec<TransactionReceipt>> {
+        let mut receipts = Vec::new();
+        for addresses in addresses.chunks(MAX_EXTEND_ADDRESSES) {
+            let instructions =
+                instructions::extend_lookup_table(lookup_table, &authority.pubkey(), &self.payer_pubkey(), addresses);
+            let receipt = self
+                .process_instructions(&instructions.instructions, &[authority])
+                .await?;
+            receipts.push(receipt);
+        }
+
+        Ok(receipts)
+    }
+
+    async fn deactivate_lookup_table(
+        &self,
+        lookup_table: &Pubkey,
+        authority: &(dyn Signer + Sync),
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::deactivate_lookup_table(lookup_table, &authority.pubkey());
+        self.process_instructions(&instructions.instructions, &[authority])
+            .await
+    }
+
+    async fn close_lookup_table(
+        &self,
+        lookup_table: &Pubkey,
+        authority: &(dyn Signer + Sync),
+        recipient: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::close_lookup_table(lookup_table, &authority.pubkey(), recipient);
+        self.process_instructions(&instructions.instructions, &[authority])
+            .await
+    }
+}
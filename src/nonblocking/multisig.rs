@@ -0,0 +1,288 @@
+use async_trait::async_trait;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Signer};
+use tracing::instrument;
+
+use crate::{
+    client::transaction_signers,
+    instructions::token as instructions,
+    multisig::{with_signer_pubkeys, PartialTransaction},
+    nonblocking::{backend::AsyncBackend, AsyncClient},
+    nonce::DurableNonceConfig,
+    AuthorityType, ClientResult, TransactionReceipt,
+};
+
+#[async_trait]
+pub trait AsyncSplTokenMultisig {
+    async fn mint_to_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt>;
+    #[allow(clippy::too_many_arguments)]
+    async fn transfer_to_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        token_mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt>;
+    #[allow(clippy::too_many_arguments)]
+    async fn approve_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        delegate: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn revoke_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn burn_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn freeze_account_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn thaw_account_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn set_authority_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        owned: &Pubkey,
+        authority_type: AuthorityType,
+        new_authority: Option<&Pubkey>,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn close_token_account_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        account: &Pubkey,
+        destination: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+}
+
+#[async_trait]
+impl<B: AsyncBackend> AsyncSplTokenMultisig for AsyncClient<B> {
+    #[instrument(skip_all, fields(multisig = %multisig, mint = %token_mint, account = %account, amount = amount))]
+    async fn mint_to_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::mint_to(multisig, signer_pubkeys, token_mint, account, amount, decimals)
+        })?;
+        self.process_instructions(&instructions.instructions, signers).await
+    }
+
+    #[instrument(
+        skip_all,
+        fields(multisig = %multisig, mint = %token_mint, source = %source, destination = %destination, amount = amount)
+    )]
+    async fn transfer_to_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        token_mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::transfer_to(
+                multisig,
+                signer_pubkeys,
+                token_mint,
+                source,
+                destination,
+                amount,
+                decimals,
+            )
+        })?;
+        self.process_instructions(&instructions.instructions, signers).await
+    }
+
+    #[instrument(
+        skip_all,
+        fields(multisig = %multisig, mint = %token_mint, account = %account, delegate = %delegate, amount = amount)
+    )]
+    async fn approve_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        delegate: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::approve(
+                multisig,
+                signer_pubkeys,
+                token_mint,
+                account,
+                delegate,
+                amount,
+                decimals,
+            )
+        })?;
+        self.process_instructions(&instructions.instructions, signers).await
+    }
+
+    #[instrument(skip_all, fields(multisig = %multisig, account = %account))]
+    async fn revoke_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::revoke(multisig, signer_pubkeys, account)
+        })?;
+        self.process_instructions(&instructions.instructions, signers).await
+    }
+
+    #[instrument(skip_all, fields(multisig = %multisig, mint = %token_mint, account = %account, amount = amount))]
+    async fn burn_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::burn(multisig, signer_pubkeys, token_mint, account, amount, decimals)
+        })?;
+        self.process_instructions(&instructions.instructions, signers).await
+    }
+
+    #[instrument(skip_all, fields(multisig = %multisig, mint = %token_mint, account = %account))]
+    async fn freeze_account_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::freeze_account(multisig, signer_pubkeys, token_mint, account)
+        })?;
+        self.process_instructions(&instructions.instructions, signers).await
+    }
+
+    #[instrument(skip_all, fields(multisig = %multisig, mint = %token_mint, account = %account))]
+    async fn thaw_account_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::thaw_account(multisig, signer_pubkeys, token_mint, account)
+        })?;
+        self.process_instructions(&instructions.instructions, signers).await
+    }
+
+    #[instrument(skip_all, fields(multisig = %multisig, owned = %owned, authority_type = ?authority_type))]
+    async fn set_authority_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        owned: &Pubkey,
+        authority_type: AuthorityType,
+        new_authority: Option<&Pubkey>,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::set_authority(multisig, signer_pubkeys, owned, authority_type, new_authority)
+        })?;
+        self.process_instructions(&instructions.instructions, signers).await
+    }
+
+    #[instrument(skip_all, fields(multisig = %multisig, account = %account, destination = %destination))]
+    async fn close_token_account_with_multisig(
+        &self,
+        multisig: &Pubkey,
+        signers: &[&(dyn Signer + Sync)],
+        account: &Pubkey,
+        destination: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = with_signer_pubkeys(signers, |signer_pubkeys| {
+            instructions::close_token_account(multisig, signer_pubkeys, account, destination)
+        })?;
+        self.process_instructions(&instructions.instructions, signers).await
+    }
+}
+
+impl<B: AsyncBackend> AsyncClient<B> {
+    /// Builds a transaction of the instructions like [`AsyncClient::prepare_transaction`], but signs it only by the
+    /// payer and the given signers, leaving the rest of the signatures to be added later.
+    pub async fn partial_transaction(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&(dyn Signer + Sync)],
+    ) -> ClientResult<PartialTransaction> {
+        let mut message = self.prepare_message(instructions, &self.compute_budget, &[]).await?;
+        message.set_recent_blockhash(self.message_blockhash(&message).await?);
+
+        let nonce_authority = self.durable_nonce.as_ref().and_then(DurableNonceConfig::authority);
+        let signers = transaction_signers(
+            &message,
+            self.payer(),
+            signers
+                .iter()
+                .map(|signer| *signer as &dyn Signer)
+                .chain(nonce_authority),
+        );
+        let mut transaction = PartialTransaction::new(message);
+        transaction.sign(&signers)?;
+        Ok(transaction)
+    }
+
+    /// Sends the transaction once it is signed by all of its signers. It is never re-signed, so it fails with
+    /// [`ClientError::Expired`](crate::ClientError::Expired) if its blockhash expires in the meantime.
+    pub async fn process_partial_transaction(
+        &self,
+        transaction: PartialTransaction,
+    ) -> ClientResult<TransactionReceipt> {
+        self.process_versioned_transaction(&transaction.into_transaction()?)
+            .await
+    }
+}
@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use solana_sdk::{
+    hash::Hash,
+    nonce::State as NonceState,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+use crate::{
+    instructions::system as instructions,
+    nonblocking::{backend::AsyncBackend, AsyncClient},
+    nonce::nonce_blockhash,
+    ClientError, ClientResult, TransactionReceipt,
+};
+
+#[async_trait]
+pub trait AsyncSystemNonce {
+    async fn create_nonce_account(&self, authority: &Pubkey, lamports: Option<u64>) -> ClientResult<Keypair>;
+    async fn get_nonce_blockhash(&self, nonce_account: &Pubkey) -> ClientResult<Hash>;
+    async fn advance_nonce_account(
+        &self,
+        nonce_account: &Pubkey,
+        authority: &(dyn Signer + Sync),
+    ) -> ClientResult<TransactionReceipt>;
+    async fn withdraw_nonce_account(
+        &self,
+        nonce_account: &Pubkey,
+        authority: &(dyn Signer + Sync),
+        destination: &Pubkey,
+        lamports: u64,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn authorize_nonce_account(
+        &self,
+        nonce_account: &Pubkey,
+        authority: &(dyn Signer + Sync),
+        new_authority: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+}
+
+#[async_trait]
+impl<B: AsyncBackend> AsyncSystemNonce for AsyncClient<B> {
+    async fn create_nonce_account(&self, authority: &Pubkey, lamports: Option<u64>) -> ClientResult<Keypair> {
+        let nonce_account = Keypair::new();
+        let lamports = if let Some(lamports) = lamports {
+            lamports
+        } else {
+            self.get_minimum_balance_for_rent_exemption(NonceState::size()).await?
+        };
+
+        let instructions =
+            instructions::create_nonce_account(&self.payer_pubkey(), &nonce_account.pubkey(), authority, lamports);
+        self.process_instructions(&instructions.instructions, &[&nonce_account])
+            .await?;
+
+        Ok(nonce_account)
+    }
+
+    async fn get_nonce_blockhash(&self, nonce_account: &Pubkey) -> ClientResult<Hash> {
+        let account = self
+            .get_account_with_commitment(nonce_account, self.commitment())
+            .await?
+            .ok_or(ClientError::AccountNotFound(*nonce_account))?;
+        nonce_blockhash(&account)
+    }
+
+    async fn advance_nonce_account(
+        &self,
+        nonce_account: &Pubkey,
+        authority: &(dyn Signer + Sync),
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::advance_nonce_account(nonce_account, &authority.pubkey());
+        self.process_instructions(&instructions.instructions, &[authority])
+            .await
+    }
+
+    async fn withdraw_nonce_account(
+        &self,
+        nonce_account: &Pubkey,
+        authority: &(dyn Signer + Sync),
+        destination: &Pubkey,
+        lamports: u64,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions =
+            instructions::withdraw_nonce_account(nonce_account, &authority.pubkey(), destination, lamports);
+        self.process_instructions(&instructions.instructions, &[authority])
+            .await
+    }
+
+    async fn authorize_nonce_account(
+        &self,
+        nonce_account: &Pubkey,
+        authority: &(dyn Signer + Sync),
+        new_authority: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::authorize_nonce_account(nonce_account, &authority.pubkey(), new_authority);
+        self.process_instructions(&instructions.instructions, &[authority])
+            .await
+    }
+}
@@ -0,0 +1,305 @@
+use std::{future::Future, pin::Pin};
+
+use async_trait::async_trait;
+use solana_sdk::{
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_token::{native_mint, state::Account as TokenAccount};
+use tracing::instrument;
+
+use crate::{
+    instructions::swap as instructions,
+    nonblocking::{backend::AsyncBackend, AsyncClient, AsyncSplToken},
+    ClientResult, Fees, SwapKeys, TransactionReceipt,
+};
+
+/// Creates a swap token account owned by the given swap authority.
+pub type TokenAccountMaker<'a> =
+    Box<dyn Fn(Pubkey) -> Pin<Box<dyn Future<Output = ClientResult<Keypair>> + Send + 'a>> + Send + Sync + 'a>;
+
+#[async_trait]
+pub trait AsyncSplSwap {
+    #[allow(clippy::too_many_arguments)]
+    async fn create_swap(
+        &self,
+        swap_program_id: &Pubkey,
+        swap_account: &(dyn Signer + Sync),
+        swap_authority_address: &Pubkey,
+        swap_authority_nonce: u8,
+        pool_token_mint_address: &Pubkey,
+        token_a_address: &Pubkey,
+        token_b_address: &Pubkey,
+        owner_address: &Pubkey,
+        fees: Fees,
+        fee_owner_address: &Pubkey,
+    ) -> ClientResult<(Keypair, Keypair)>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn create_swap_and_init(
+        &self,
+        swap_program_id: &Pubkey,
+        owner: &(dyn Signer + Sync),
+        token_a_mint_address: &Pubkey,
+        token_a_maker: Option<TokenAccountMaker<'_>>,
+        token_b_mint_address: &Pubkey,
+        token_b_maker: Option<TokenAccountMaker<'_>>,
+        pool_token_decimals: u8,
+        fees: Fees,
+        fee_owner_address: &Pubkey,
+    ) -> ClientResult<SwapKeys>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn swap(
+        &self,
+        swap_program_id: &Pubkey,
+        swap_account_address: &Pubkey,
+        swap_authority_address: &Pubkey,
+        user_transfer_authority: &(dyn Signer + Sync),
+        source_address: &Pubkey,
+        pool_source_address: &Pubkey,
+        pool_destination_address: &Pubkey,
+        destination_address: &Pubkey,
+        pool_token_mint_address: &Pubkey,
+        fee_account_address: &Pubkey,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> ClientResult<TransactionReceipt>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn swap_wrapping_sol(
+        &self,
+        swap_program_id: &Pubkey,
+        swap_account_address: &Pubkey,
+        swap_authority_address: &Pubkey,
+        user_transfer_authority: &(dyn Signer + Sync),
+        source_address: &Pubkey,
+        pool_source_address: &Pubkey,
+        pool_destination_address: &Pubkey,
+        destination_address: &Pubkey,
+        pool_token_mint_address: &Pubkey,
+        fee_account_address: &Pubkey,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> ClientResult<TransactionReceipt>;
+}
+
+#[async_trait]
+impl<B: AsyncBackend> AsyncSplSwap for AsyncClient<B> {
+    #[instrument(skip_all, fields(swap = %swap_account.pubkey(), pool_mint = %pool_token_mint_address))]
+    async fn create_swap(
+        &self,
+        swap_program_id: &Pubkey,
+        swap_account: &(dyn Signer + Sync),
+        swap_authority_address: &Pubkey,
+        swap_authority_nonce: u8,
+        pool_token_mint_address: &Pubkey,
+        token_a_address: &Pubkey,
+        token_b_address: &Pubkey,
+        owner_address: &Pubkey,
+        fees: Fees,
+        fee_account_owner_address: &Pubkey,
+    ) -> ClientResult<(Keypair, Keypair)> {
+        let fee_account = Keypair::new();
+        let pool_token_initial_supply_account = Keypair::new();
+
+        let instructions = instructions::create_swap(
+            &self.payer_pubkey(),
+            swap_program_id,
+            &swap_account.pubkey(),
+            swap_authority_address,
+            swap_authority_nonce,
+            pool_token_mint_address,
+            token_a_address,
+            token_b_address,
+            &fee_account.pubkey(),
+            &pool_token_initial_supply_account.pubkey(),
+            owner_address,
+            fees,
+            fee_account_owner_address,
+            self.get_minimum_balance_for_rent_exemption(TokenAccount::LEN).await?,
+        )?;
+        self.process_instructions(&instructions.instructions, &[
+            swap_account,
+            &fee_account,
+            &pool_token_initial_supply_account,
+        ])
+        .await?;
+
+        Ok((fee_account, pool_token_initial_supply_account))
+    }
+
+    #[instrument(skip_all, fields(mint_a = %token_a_mint_address, mint_b = %token_b_mint_address))]
+    async fn create_swap_and_init(
+        &self,
+        swap_program_id: &Pubkey,
+        owner: &(dyn Signer + Sync),
+        token_a_mint_address: &Pubkey,
+        token_a_maker: Option<TokenAccountMaker<'_>>,
+        token_b_mint_address: &Pubkey,
+        token_b_maker: Option<TokenAccountMaker<'_>>,
+        pool_token_decimals: u8,
+        fees: Fees,
+        fee_owner_address: &Pubkey,
+    ) -> ClientResult<SwapKeys> {
+        let swap_account = self
+            .create_account(swap_program_id, spl_token_swap::state::SwapVersion::LATEST_LEN, None)
+            .await?;
+
+        let (swap_authority_address, swap_authority_nonce) =
+            Pubkey::find_program_address(&[swap_account.pubkey().as_ref()], swap_program_id);
+
+        let token_a = if let Some(maker) = token_a_maker {
+            maker(swap_authority_address).await?
+        } else {
+            self.create_token_account(&swap_authority_address, token_a_mint_address)
+                .await?
+        };
+
+        let token_b = if let Some(maker) = token_b_maker {
+            maker(swap_authority_address).await?
+        } else {
+            self.create_token_account(&swap_authority_address, token_b_mint_address)
+                .await?
+        };
+
+        let pool_token_mint = self
+            .create_token_mint(&swap_authority_address, pool_token_decimals)
+            .await?;
+
+        let (fee_account, pool_token_initial_supply_account) = self
+            .create_swap(
+                swap_program_id,
+                &swap_account,
+                &swap_authority_address,
+                swap_authority_nonce,
+                &pool_token_mint.pubkey(),
+                &token_a.pubkey(),
+                &token_b.pubkey(),
+                &owner.pubkey(),
+                fees,
+                fee_owner_address,
+            )
+            .await?;
+
+        Ok(SwapKeys {
+            swap: swap_account,
+            authority_address: swap_authority_address,
+            authority_nonce: swap_authority_nonce,
+            token_a,
+            token_b,
+            pool_token_mint,
+            fee_account,
+            pool_token_initial_supply_account,
+        })
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            swap = %swap_account_address,
+            pool_mint = %pool_token_mint_address,
+            amount_in = amount_in,
+            minimum_amount_out = minimum_amount_out
+        )
+    )]
+    async fn swap(
+        &self,
+        swap_program_id: &Pubkey,
+        swap_account_address: &Pubkey,
+        swap_authority_address: &Pubkey,
+        user_transfer_authority: &(dyn Signer + Sync),
+        source_address: &Pubkey,
+        pool_source_address: &Pubkey,
+        pool_destination_address: &Pubkey,
+        destination_address: &Pubkey,
+        pool_token_mint_address: &Pubkey,
+        fee_account_address: &Pubkey,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::swap(
+            swap_program_id,
+            swap_account_address,
+            swap_authority_address,
+            &user_transfer_authority.pubkey(),
+            source_address,
+            pool_source_address,
+            pool_destination_address,
+            destination_address,
+            pool_token_mint_address,
+            fee_account_address,
+            amount_in,
+            minimum_amount_out,
+        )?;
+
+        self.process_instructions(&instructions.instructions, &[user_transfer_authority])
+            .await
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            swap = %swap_account_address,
+            pool_mint = %pool_token_mint_address,
+            amount_in = amount_in,
+            minimum_amount_out = minimum_amount_out
+        )
+    )]
+    async fn swap_wrapping_sol(
+        &self,
+        swap_program_id: &Pubkey,
+        swap_account_address: &Pubkey,
+        swap_authority_address: &Pubkey,
+        user_transfer_authority: &(dyn Signer + Sync),
+        source_address: &Pubkey,
+        pool_source_address: &Pubkey,
+        pool_destination_address: &Pubkey,
+        destination_address: &Pubkey,
+        pool_token_mint_address: &Pubkey,
+        fee_account_address: &Pubkey,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> ClientResult<TransactionReceipt> {
+        let pool_accounts = self
+            .get_multiple_token_accounts(&[*pool_source_address, *pool_destination_address])
+            .await?;
+        let is_native = |account: &Option<TokenAccount>| {
+            account
+                .as_ref()
+                .map_or(false, |account| account.mint == native_mint::id())
+        };
+        let wrapped_source = is_native(&pool_accounts[0]).then(Keypair::new);
+        let wrapped_destination = is_native(&pool_accounts[1]).then(Keypair::new);
+
+        let instructions = instructions::swap_wrapping_sol(
+            swap_program_id,
+            swap_account_address,
+            swap_authority_address,
+            &user_transfer_authority.pubkey(),
+            &wrapped_source.as_ref().map_or(*source_address, Keypair::pubkey),
+            pool_source_address,
+            pool_destination_address,
+            &wrapped_destination
+                .as_ref()
+                .map_or(*destination_address, Keypair::pubkey),
+            pool_token_mint_address,
+            fee_account_address,
+            amount_in,
+            minimum_amount_out,
+            wrapped_source.is_some(),
+            wrapped_destination.is_some(),
+            self.get_minimum_balance_for_rent_exemption(TokenAccount::LEN).await?,
+        )?;
+
+        let mut signers = vec![user_transfer_authority];
+        signers.extend(wrapped_source.iter().map(|keypair| keypair as &(dyn Signer + Sync)));
+        signers.extend(
+            wrapped_destination
+                .iter()
+                .map(|keypair| keypair as &(dyn Signer + Sync)),
+        );
+        self.process_instructions(&instructions.instructions, &signers).await
+    }
+}
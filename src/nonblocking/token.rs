@@ -0,0 +1,496 @@
+use async_trait::async_trait;
+use solana_client::rpc_request::MAX_MULTIPLE_ACCOUNTS;
+use solana_sdk::{
+    instruction::Instruction,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_token::{
+    native_mint,
+    state::{Account as TokenAccount, Mint, Multisig},
+};
+use tracing::instrument;
+
+use crate::{
+    instructions::token as instructions,
+    nonblocking::{backend::AsyncBackend, AsyncClient},
+    token::unpack_token_state,
+    AuthorityType, ClientError, ClientResult, TokenAmount, TransactionReceipt,
+};
+
+#[async_trait]
+pub trait AsyncSplToken {
+    async fn get_mint(&self, token_mint: &Pubkey) -> ClientResult<Mint>;
+    async fn get_token_account(&self, account: &Pubkey) -> ClientResult<TokenAccount>;
+    async fn get_multisig(&self, multisig: &Pubkey) -> ClientResult<Multisig>;
+    async fn get_multiple_token_accounts(&self, accounts: &[Pubkey]) -> ClientResult<Vec<Option<TokenAccount>>>;
+    async fn get_mint_decimals(&self, token_mint: &Pubkey) -> ClientResult<u8>;
+    async fn create_token_mint(&self, owner: &Pubkey, decimals: u8) -> ClientResult<Keypair>;
+    async fn create_token_mint_with_freeze_authority(
+        &self,
+        owner: &Pubkey,
+        freeze_authority: Option<&Pubkey>,
+        decimals: u8,
+    ) -> ClientResult<Keypair>;
+    async fn create_multisig(&self, signers: &[&Pubkey], m: u8) -> ClientResult<Keypair>;
+    async fn create_token_account(&self, owner: &Pubkey, token_mint: &Pubkey) -> ClientResult<Keypair>;
+    async fn create_token_account_with_lamports(
+        &self,
+        owner: &Pubkey,
+        token_mint: &Pubkey,
+        lamports: u64,
+    ) -> ClientResult<Keypair>;
+    async fn mint_to(
+        &self,
+        owner: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn transfer_to(
+        &self,
+        owner: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn mint_amount_to(
+        &self,
+        owner: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: TokenAmount,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn transfer_amount_to(
+        &self,
+        owner: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        amount: TokenAmount,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn approve(
+        &self,
+        owner: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        delegate: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn revoke(&self, owner: &(dyn Signer + Sync), account: &Pubkey) -> ClientResult<TransactionReceipt>;
+    async fn burn(
+        &self,
+        authority: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn freeze_account(
+        &self,
+        freeze_authority: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn thaw_account(
+        &self,
+        freeze_authority: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn set_authority(
+        &self,
+        authority: &(dyn Signer + Sync),
+        owned: &Pubkey,
+        authority_type: AuthorityType,
+        new_authority: Option<&Pubkey>,
+    ) -> ClientResult<TransactionReceipt>;
+    fn get_associated_token_address(wallet_address: &Pubkey, token_mint: &Pubkey) -> Pubkey;
+    async fn create_associated_token_account(
+        &self,
+        funder: &(dyn Signer + Sync),
+        recipient: &Pubkey,
+        token_mint: &Pubkey,
+    ) -> ClientResult<Pubkey>;
+    async fn create_associated_token_account_by_payer(
+        &self,
+        recipient: &Pubkey,
+        token_mint: &Pubkey,
+    ) -> ClientResult<Pubkey>;
+    async fn close_token_account(
+        &self,
+        owner: &(dyn Signer + Sync),
+        account: &Pubkey,
+        destination: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+    async fn wrap_sol(&self, owner: &(dyn Signer + Sync), lamports: u64) -> ClientResult<TransactionReceipt>;
+    async fn unwrap_sol(&self, owner: &(dyn Signer + Sync)) -> ClientResult<TransactionReceipt>;
+    async fn sync_native(&self, account: &Pubkey) -> ClientResult<TransactionReceipt>;
+}
+
+#[async_trait]
+impl<B: AsyncBackend> AsyncSplToken for AsyncClient<B> {
+    #[instrument(skip_all, fields(mint = %token_mint))]
+    async fn get_mint(&self, token_mint: &Pubkey) -> ClientResult<Mint> {
+        let account = self
+            .get_account_with_commitment(token_mint, self.commitment())
+            .await?
+            .ok_or(ClientError::AccountNotFound(*token_mint))?;
+        unpack_token_state(token_mint, &account)
+    }
+
+    #[instrument(skip_all, fields(account = %account))]
+    async fn get_token_account(&self, account: &Pubkey) -> ClientResult<TokenAccount> {
+        let token_account = self
+            .get_account_with_commitment(account, self.commitment())
+            .await?
+            .ok_or(ClientError::AccountNotFound(*account))?;
+        unpack_token_state(account, &token_account)
+    }
+
+    #[instrument(skip_all, fields(multisig = %multisig))]
+    async fn get_multisig(&self, multisig: &Pubkey) -> ClientResult<Multisig> {
+        let account = self
+            .get_account_with_commitment(multisig, self.commitment())
+            .await?
+            .ok_or(ClientError::AccountNotFound(*multisig))?;
+        unpack_token_state(multisig, &account)
+    }
+
+    #[instrument(skip_all, fields(accounts = accounts.len()))]
+    async fn get_multiple_token_accounts(&self, accounts: &[Pubkey]) -> ClientResult<Vec<Option<TokenAccount>>> {
+        let mut token_accounts = Vec::with_capacity(accounts.len());
+        for pubkeys in accounts.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            for (pubkey, account) in pubkeys.iter().zip(self.get_multiple_accounts(pubkeys).await?) {
+                token_accounts.push(
+                    account
+                        .map(|account| unpack_token_state(pubkey, &account))
+                        .transpose()?,
+                );
+            }
+        }
+
+        Ok(token_accounts)
+    }
+
+    #[instrument(skip_all, fields(mint = %token_mint))]
+    async fn get_mint_decimals(&self, token_mint: &Pubkey) -> ClientResult<u8> {
+        if let Some(decimals) = self.decimals_cache.get(token_mint) {
+            return Ok(decimals);
+        }
+
+        let decimals = self.get_mint(token_mint).await?.decimals;
+        self.decimals_cache.insert(*token_mint, decimals);
+        Ok(decimals)
+    }
+
+    #[instrument(skip_all, fields(owner = %owner, decimals = decimals))]
+    async fn create_token_mint(&self, owner: &Pubkey, decimals: u8) -> ClientResult<Keypair> {
+        self.create_token_mint_with_freeze_authority(owner, None, decimals)
+            .await
+    }
+
+    #[instrument(skip_all, fields(owner = %owner, decimals = decimals))]
+    async fn create_token_mint_with_freeze_authority(
+        &self,
+        owner: &Pubkey,
+        freeze_authority: Option<&Pubkey>,
+        decimals: u8,
+    ) -> ClientResult<Keypair> {
+        let token_mint = Keypair::new();
+
+        let instructions = instructions::create_token_mint_with_freeze_authority(
+            &self.payer_pubkey(),
+            &token_mint.pubkey(),
+            owner,
+            freeze_authority,
+            decimals,
+            self.get_minimum_balance_for_rent_exemption(Mint::LEN).await?,
+        )?;
+        self.process_instructions(&instructions.instructions, &[&token_mint])
+            .await?;
+
+        Ok(token_mint)
+    }
+
+    #[instrument(skip_all, fields(signers = signers.len(), m = m))]
+    async fn create_multisig(&self, signers: &[&Pubkey], m: u8) -> ClientResult<Keypair> {
+        let multisig = Keypair::new();
+
+        let instructions = instructions::create_multisig(
+            &self.payer_pubkey(),
+            &multisig.pubkey(),
+            signers,
+            m,
+            self.get_minimum_balance_for_rent_exemption(Multisig::LEN).await?,
+        )?;
+        self.process_instructions(&instructions.instructions, &[&multisig])
+            .await?;
+
+        Ok(multisig)
+    }
+
+    #[instrument(skip_all, fields(owner = %owner, mint = %token_mint))]
+    async fn create_token_account(&self, owner: &Pubkey, token_mint: &Pubkey) -> ClientResult<Keypair> {
+        self.create_token_account_with_lamports(
+            owner,
+            token_mint,
+            self.get_minimum_balance_for_rent_exemption(TokenAccount::LEN).await?,
+        )
+        .await
+    }
+
+    #[instrument(skip_all, fields(owner = %owner, mint = %token_mint, lamports = lamports))]
+    async fn create_token_account_with_lamports(
+        &self,
+        owner: &Pubkey,
+        token_mint: &Pubkey,
+        lamports: u64,
+    ) -> ClientResult<Keypair> {
+        let token_account = Keypair::new();
+
+        let instructions = instructions::create_token_account(
+            &self.payer_pubkey(),
+            &token_account.pubkey(),
+            owner,
+            token_mint,
+            lamports,
+        )?;
+        self.process_instructions(&instructions.instructions, &[&token_account])
+            .await?;
+
+        Ok(token_account)
+    }
+
+    #[instrument(skip_all, fields(mint = %token_mint, account = %account, amount = amount))]
+    async fn mint_to(
+        &self,
+        owner: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::mint_to(&owner.pubkey(), &[], token_mint, account, amount, decimals)?;
+        self.process_instructions(&instructions.instructions, &[owner]).await
+    }
+
+    #[instrument(skip_all, fields(mint = %token_mint, source = %source, destination = %destination, amount = amount))]
+    async fn transfer_to(
+        &self,
+        authority: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::transfer_to(
+            &authority.pubkey(),
+            &[],
+            token_mint,
+            source,
+            destination,
+            amount,
+            decimals,
+        )?;
+        self.process_instructions(&instructions.instructions, &[authority])
+            .await
+    }
+
+    #[instrument(skip_all, fields(mint = %token_mint, account = %account, amount = ?amount))]
+    async fn mint_amount_to(
+        &self,
+        owner: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: TokenAmount,
+    ) -> ClientResult<TransactionReceipt> {
+        let decimals = self.get_mint_decimals(token_mint).await?;
+        self.mint_to(owner, token_mint, account, amount.to_units(decimals)?, decimals)
+            .await
+    }
+
+    #[instrument(skip_all, fields(mint = %token_mint, source = %source, destination = %destination, amount = ?amount))]
+    async fn transfer_amount_to(
+        &self,
+        authority: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        amount: TokenAmount,
+    ) -> ClientResult<TransactionReceipt> {
+        let decimals = self.get_mint_decimals(token_mint).await?;
+        self.transfer_to(
+            authority,
+            token_mint,
+            source,
+            destination,
+            amount.to_units(decimals)?,
+            decimals,
+        )
+        .await
+    }
+
+    #[instrument(skip_all, fields(mint = %token_mint, account = %account, delegate = %delegate, amount = amount))]
+    async fn approve(
+        &self,
+        owner: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        delegate: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions =
+            instructions::approve(&owner.pubkey(), &[], token_mint, account, delegate, amount, decimals)?;
+        self.process_instructions(&instructions.instructions, &[owner]).await
+    }
+
+    #[instrument(skip_all, fields(account = %account))]
+    async fn revoke(&self, owner: &(dyn Signer + Sync), account: &Pubkey) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::revoke(&owner.pubkey(), &[], account)?;
+        self.process_instructions(&instructions.instructions, &[owner]).await
+    }
+
+    #[instrument(skip_all, fields(mint = %token_mint, account = %account, amount = amount))]
+    async fn burn(
+        &self,
+        authority: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::burn(&authority.pubkey(), &[], token_mint, account, amount, decimals)?;
+        self.process_instructions(&instructions.instructions, &[authority])
+            .await
+    }
+
+    #[instrument(skip_all, fields(mint = %token_mint, account = %account))]
+    async fn freeze_account(
+        &self,
+        freeze_authority: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::freeze_account(&freeze_authority.pubkey(), &[], token_mint, account)?;
+        self.process_instructions(&instructions.instructions, &[freeze_authority])
+            .await
+    }
+
+    #[instrument(skip_all, fields(mint = %token_mint, account = %account))]
+    async fn thaw_account(
+        &self,
+        freeze_authority: &(dyn Signer + Sync),
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::thaw_account(&freeze_authority.pubkey(), &[], token_mint, account)?;
+        self.process_instructions(&instructions.instructions, &[freeze_authority])
+            .await
+    }
+
+    #[instrument(skip_all, fields(owned = %owned, authority_type = ?authority_type))]
+    async fn set_authority(
+        &self,
+        authority: &(dyn Signer + Sync),
+        owned: &Pubkey,
+        authority_type: AuthorityType,
+        new_authority: Option<&Pubkey>,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::set_authority(&authority.pubkey(), &[], owned, authority_type, new_authority)?;
+        self.process_instructions(&instructions.instructions, &[authority])
+            .await
+    }
+
+    fn get_associated_token_address(wallet_address: &Pubkey, token_mint: &Pubkey) -> Pubkey {
+        spl_associated_token_account::get_associated_token_address(wallet_address, token_mint)
+    }
+
+    #[instrument(skip_all, fields(recipient = %recipient, mint = %token_mint))]
+    async fn create_associated_token_account(
+        &self,
+        funder: &(dyn Signer + Sync),
+        recipient: &Pubkey,
+        token_mint: &Pubkey,
+    ) -> ClientResult<Pubkey> {
+        let instructions = instructions::create_associated_token_account(&funder.pubkey(), recipient, token_mint);
+        self.process_instructions(&instructions.instructions, &[funder]).await?;
+
+        Ok(Self::get_associated_token_address(recipient, token_mint))
+    }
+
+    #[instrument(skip_all, fields(recipient = %recipient, mint = %token_mint))]
+    async fn create_associated_token_account_by_payer(
+        &self,
+        recipient: &Pubkey,
+        token_mint: &Pubkey,
+    ) -> ClientResult<Pubkey> {
+        self.create_associated_token_account(self.payer(), recipient, token_mint)
+            .await
+    }
+
+    #[instrument(skip_all, fields(account = %account, destination = %destination))]
+    async fn close_token_account(
+        &self,
+        owner: &(dyn Signer + Sync),
+        account: &Pubkey,
+        destination: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::close_token_account(&owner.pubkey(), &[], account, destination)?;
+        self.process_instructions(&instructions.instructions, &[owner]).await
+    }
+
+    #[instrument(skip_all, fields(owner = %owner.pubkey(), lamports = lamports))]
+    async fn wrap_sol(&self, owner: &(dyn Signer + Sync), lamports: u64) -> ClientResult<TransactionReceipt> {
+        let account = Self::get_associated_token_address(&owner.pubkey(), &native_mint::id());
+        let create = self
+            .create_native_account_instructions(&owner.pubkey(), &account)
+            .await?;
+        let wrap = instructions::wrap_sol(&owner.pubkey(), &account, lamports)?;
+        self.process_instructions(&[create, wrap.instructions].concat(), &[owner])
+            .await
+    }
+
+    #[instrument(skip_all, fields(owner = %owner.pubkey()))]
+    async fn unwrap_sol(&self, owner: &(dyn Signer + Sync)) -> ClientResult<TransactionReceipt> {
+        let account = Self::get_associated_token_address(&owner.pubkey(), &native_mint::id());
+        let instructions = instructions::unwrap_sol(&owner.pubkey(), &[], &account, &owner.pubkey())?;
+        self.process_instructions(&instructions.instructions, &[owner]).await
+    }
+
+    #[instrument(skip_all, fields(account = %account))]
+    async fn sync_native(&self, account: &Pubkey) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::sync_native(account)?;
+        self.process_instructions(&instructions.instructions, &[]).await
+    }
+}
+
+impl<B: AsyncBackend> AsyncClient<B> {
+    /// Returns the instructions creating the token account of the native mint, none if it exists already. Only the
+    /// associated token account of the owner is created, any other missing account fails.
+    pub(crate) async fn create_native_account_instructions(
+        &self,
+        owner: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<Vec<Instruction>> {
+        if self
+            .get_account_with_commitment(account, self.commitment())
+            .await?
+            .is_some()
+        {
+            return Ok(Vec::new());
+        }
+        if *account != Self::get_associated_token_address(owner, &native_mint::id()) {
+            return Err(ClientError::AccountNotFound(*account));
+        }
+
+        Ok(instructions::create_associated_token_account(&self.payer_pubkey(), owner, &native_mint::id()).instructions)
+    }
+}
@@ -0,0 +1,222 @@
+use solana_client::nonce_utils;
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    instruction::Instruction,
+    message::VersionedMessage,
+    nonce::State as NonceState,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction::{self, SystemInstruction},
+    system_program,
+};
+
+use crate::{
+    backend::Backend, instructions::system as instructions, Client, ClientError, ClientResult, TransactionReceipt,
+};
+
+/// Durable nonce used by the client instead of the latest blockhash for every transaction it builds.
+pub struct DurableNonceConfig {
+    pub nonce_account: Pubkey,
+
+    /// Authority of the nonce account, the payer is used when `None`.
+    pub authority: Option<Box<dyn Signer + Send + Sync>>,
+}
+
+impl DurableNonceConfig {
+    pub fn new(nonce_account: Pubkey) -> Self {
+        Self {
+            nonce_account,
+            authority: None,
+        }
+    }
+
+    pub fn with_authority(mut self, authority: impl Signer + Send + Sync + 'static) -> Self {
+        self.authority = Some(Box::new(authority));
+        self
+    }
+
+    pub fn authority_pubkey(&self, payer: &Pubkey) -> Pubkey {
+        self.authority.as_ref().map_or(*payer, |authority| authority.pubkey())
+    }
+
+    pub fn authority(&self) -> Option<&dyn Signer> {
+        self.authority.as_deref().map(|authority| authority as &dyn Signer)
+    }
+
+    /// Instruction which has to go first in every transaction using the nonce.
+    pub fn advance_instruction(&self, payer: &Pubkey) -> Instruction {
+        system_instruction::advance_nonce_account(&self.nonce_account, &self.authority_pubkey(payer))
+    }
+
+    /// Whether the instruction advances the nonce account.
+    pub fn is_advance_instruction(&self, instruction: &Instruction) -> bool {
+        system_program::check_id(&instruction.program_id)
+            && instruction.accounts.first().map(|account| account.pubkey) == Some(self.nonce_account)
+            && matches!(
+                bincode::deserialize(&instruction.data),
+                Ok(SystemInstruction::AdvanceNonceAccount)
+            )
+    }
+
+    /// Puts the advance instruction first, moving the one of the instructions if they already advance the nonce
+    /// account, so that the nonce is advanced exactly once.
+    pub fn apply(&self, mut instructions: Vec<Instruction>, payer: &Pubkey) -> Vec<Instruction> {
+        let advance = match instructions
+            .iter()
+            .position(|instruction| self.is_advance_instruction(instruction))
+        {
+            Some(index) => instructions.remove(index),
+            None => self.advance_instruction(payer),
+        };
+        instructions.insert(0, advance);
+        instructions
+    }
+}
+
+/// Returns the nonce account of a durable nonce transaction, i.e. one starting with the nonce advance instruction.
+pub fn nonce_account_of(message: &VersionedMessage) -> Option<Pubkey> {
+    let account_keys = message.static_account_keys();
+    let instruction = message.instructions().first()?;
+    let advances_nonce = system_program::check_id(instruction.program_id(account_keys))
+        && matches!(
+            bincode::deserialize(&instruction.data),
+            Ok(SystemInstruction::AdvanceNonceAccount)
+        );
+    if !advances_nonce {
+        return None;
+    }
+
+    let index = instruction.accounts.first()?;
+    account_keys.get(usize::from(*index)).copied()
+}
+
+/// Returns the blockhash stored in the nonce account.
+pub fn nonce_blockhash(account: &Account) -> ClientResult<Hash> {
+    Ok(nonce_utils::data_from_account(account)?.blockhash())
+}
+
+pub trait SystemNonce {
+    fn create_nonce_account(&self, authority: &Pubkey, lamports: Option<u64>) -> ClientResult<Keypair>;
+    fn get_nonce_blockhash(&self, nonce_account: &Pubkey) -> ClientResult<Hash>;
+    fn advance_nonce_account(&self, nonce_account: &Pubkey, authority: &dyn Signer)
+        -> ClientResult<TransactionReceipt>;
+    fn withdraw_nonce_account(
+        &self,
+        nonce_account: &Pubkey,
+        authority: &dyn Signer,
+        destination: &Pubkey,
+        lamports: u64,
+    ) -> ClientResult<TransactionReceipt>;
+    fn authorize_nonce_account(
+        &self,
+        nonce_account: &Pubkey,
+        authority: &dyn Signer,
+        new_authority: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+}
+
+impl<B: Backend> SystemNonce for Client<B> {
+    fn create_nonce_account(&self, authority: &Pubkey, lamports: Option<u64>) -> ClientResult<Keypair> {
+        let nonce_account = Keypair::new();
+        let lamports = if let Some(lamports) = lamports {
+            lamports
+        } else {
+            self.get_minimum_balance_for_rent_exemption(NonceState::size())?
+        };
+
+        let instructions =
+            instructions::create_nonce_account(&self.payer_pubkey(), &nonce_account.pubkey(), authority, lamports);
+        self.process_instructions(&instructions.instructions, &[&nonce_account])?;
+
+        Ok(nonce_account)
+    }
+
+    fn get_nonce_blockhash(&self, nonce_account: &Pubkey) -> ClientResult<Hash> {
+        let account = self
+            .get_account_with_commitment(nonce_account, self.commitment())?
+            .ok_or(ClientError::AccountNotFound(*nonce_account))?;
+        nonce_blockhash(&account)
+    }
+
+    fn advance_nonce_account(
+        &self,
+        nonce_account: &Pubkey,
+        authority: &dyn Signer,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::advance_nonce_account(nonce_account, &authority.pubkey());
+        self.process_instructions(&instructions.instructions, &[authority])
+    }
+
+    fn withdraw_nonce_account(
+        &self,
+        nonce_account: &Pubkey,
+        authority: &dyn Signer,
+        destination: &Pubkey,
+        lamports: u64,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions =
+            instructions::withdraw_nonce_account(nonce_account, &authority.pubkey(), destination, lamports);
+        self.process_instructions(&instructions.instructions, &[authority])
+    }
+
+    fn authorize_nonce_account(
+        &self,
+        nonce_account: &Pubkey,
+        authority: &dyn Signer,
+        new_authority: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::authorize_nonce_account(nonce_account, &authority.pubkey(), new_authority);
+        self.process_instructions(&instructions.instructions, &[authority])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::nonce::state::{DurableNonce, Versions};
+
+    use super::*;
+    use crate::{mock::MockBackend, ComputeBudget};
+
+    fn nonce_account(authority: &Pubkey) -> Account {
+        let state = NonceState::new_initialized(authority, DurableNonce::from_blockhash(&Hash::new_unique()), 5000);
+        Account::new_data(1_000_000, &Versions::new(state), &system_program::id()).unwrap()
+    }
+
+    fn advances(client: &Client<MockBackend>) -> Vec<usize> {
+        client
+            .instructions()
+            .iter()
+            .enumerate()
+            .filter(|(_, instruction)| matches!(instruction.system(), Some(SystemInstruction::AdvanceNonceAccount)))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    #[test]
+    fn durable_nonce_is_advanced_first() {
+        let payer = Keypair::new();
+        let nonce = Pubkey::new_unique();
+        let backend = MockBackend::new().with_account(nonce, nonce_account(&payer.pubkey()));
+        let client = Client::new(backend, payer).with_durable_nonce(DurableNonceConfig::new(nonce));
+
+        let transfer = system_instruction::transfer(&client.payer_pubkey(), &Pubkey::new_unique(), 100);
+        client.process_instructions(&[transfer], &[]).unwrap();
+
+        assert_eq!(advances(&client), vec![0]);
+    }
+
+    #[test]
+    fn advancing_durable_nonce_advances_it_once() {
+        let payer = Keypair::new();
+        let nonce = Pubkey::new_unique();
+        let backend = MockBackend::new().with_account(nonce, nonce_account(&payer.pubkey()));
+        let client = Client::new(backend, payer)
+            .with_durable_nonce(DurableNonceConfig::new(nonce))
+            .with_compute_budget(ComputeBudget::default().with_unit_limit(10_000));
+
+        client.advance_nonce_account(&nonce, client.payer()).unwrap();
+
+        assert_eq!(advances(&client), vec![0]);
+    }
+}
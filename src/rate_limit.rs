@@ -0,0 +1,419 @@
+//! Client-side rate limiting of the RPC requests, keeping bulk helpers under the limits of the provider.
+//!
+//! Every RPC method has a token bucket of its own, limited either by a limit of the method or by the default one.
+//! The limiter counts the requests of every method, so that scripts can see how many of them a helper consumed:
+//!
+//! ```no_run
+//! use solana_client::rpc_request::RpcRequest;
+//! use solana_client_helpers::{
+//!     rate_limit::{RateLimit, RateLimiter, RateLimits},
+//!     ClientBuilder, SplToken,
+//! };
+//! # use solana_sdk::pubkey::Pubkey;
+//! # let (owners, token_mint): (Vec<Pubkey>, Pubkey) = (vec![], Pubkey::new_unique());
+//!
+//! let limiter = RateLimiter::new(
+//!     RateLimits::new(RateLimit::per_second(40)).with_method(RpcRequest::SendTransaction, RateLimit::per_second(5)),
+//! );
+//! let client = ClientBuilder::new()
+//!     .url("mainnet-beta")
+//!     .payer_file("payer.json")
+//!     .rate_limiter(limiter.clone())
+//!     .build()?;
+//!
+//! for owner in &owners {
+//!     client.create_associated_token_account_by_payer(owner, &token_mint)?;
+//! }
+//! println!("{} requests", limiter.counters().requests());
+//! # Ok::<(), solana_client_helpers::ClientError>(())
+//! ```
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use serde_json::Value;
+use solana_client::{
+    client_error::{self, ClientErrorKind},
+    http_sender::HttpSender,
+    rpc_request::RpcRequest,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+
+/// HTTP status of the responses to the requests exceeding the limits of the provider.
+const TOO_MANY_REQUESTS: u16 = 429;
+
+/// Sustained rate of requests together with the number of requests which may be made at once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub requests_per_second: f64,
+    pub burst: u32,
+}
+
+impl RateLimit {
+    pub fn per_second(requests: u32) -> Self {
+        Self {
+            requests_per_second: f64::from(requests),
+            burst: requests.max(1),
+        }
+    }
+
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+}
+
+/// Limits of the RPC methods.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimits {
+    /// Limit of the methods without a limit of their own, unlimited when not set.
+    pub default: Option<RateLimit>,
+    pub methods: HashMap<RpcRequest, RateLimit>,
+
+    /// Pause of a method once the provider keeps rejecting its requests with `429 Too Many Requests`.
+    pub cooldown: Duration,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            default: None,
+            methods: HashMap::new(),
+            cooldown: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RateLimits {
+    pub fn new(default: RateLimit) -> Self {
+        Self {
+            default: Some(default),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_method(mut self, request: RpcRequest, limit: RateLimit) -> Self {
+        self.methods.insert(request, limit);
+        self
+    }
+
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    fn limit(&self, request: RpcRequest) -> Option<RateLimit> {
+        self.methods.get(&request).copied().or(self.default)
+    }
+}
+
+/// Counters of the requests of an RPC method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MethodCounters {
+    pub requests: u64,
+
+    /// Requests the provider answered with `429 Too Many Requests` at least once.
+    pub rate_limited: u64,
+
+    /// Time the requests waited for the limiter and for the provider.
+    pub delayed: Duration,
+}
+
+/// Counters of the requests by RPC method.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestCounters {
+    methods: HashMap<RpcRequest, MethodCounters>,
+}
+
+impl RequestCounters {
+    pub fn get(&self, request: RpcRequest) -> MethodCounters {
+        self.methods.get(&request).copied().unwrap_or_default()
+    }
+
+    /// Returns the total number of requests.
+    pub fn requests(&self) -> u64 {
+        self.methods.values().map(|counters| counters.requests).sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (RpcRequest, MethodCounters)> + '_ {
+        self.methods.iter().map(|(request, counters)| (*request, *counters))
+    }
+
+    /// Returns the counters of the requests made after the earlier snapshot.
+    pub fn since(&self, earlier: &Self) -> Self {
+        let methods = self
+            .iter()
+            .map(|(request, counters)| {
+                let earlier = earlier.get(request);
+                let counters = MethodCounters {
+                    requests: counters.requests.saturating_sub(earlier.requests),
+                    rate_limited: counters.rate_limited.saturating_sub(earlier.rate_limited),
+                    delayed: counters.delayed.saturating_sub(earlier.delayed),
+                };
+                (request, counters)
+            })
+            .filter(|(_, counters)| *counters != MethodCounters::default())
+            .collect();
+        Self { methods }
+    }
+
+    fn entry(&mut self, request: RpcRequest) -> &mut MethodCounters {
+        self.methods.entry(request).or_default()
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl Bucket {
+    fn new(limit: Option<RateLimit>) -> Self {
+        Self {
+            tokens: limit.map_or(0.0, |limit| f64::from(limit.burst)),
+            refilled_at: Instant::now(),
+            paused_until: None,
+        }
+    }
+
+    /// Takes a token, returning how long to wait for one when there are none left.
+    fn take(&mut self, limit: Option<RateLimit>, now: Instant) -> Option<Duration> {
+        if let Some(paused_until) = self.paused_until {
+            if now < paused_until {
+                return Some(paused_until - now);
+            }
+            self.paused_until = None;
+        }
+
+        let limit = limit?;
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit.requests_per_second).min(f64::from(limit.burst));
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64(
+                (1.0 - self.tokens) / limit.requests_per_second.max(f64::EPSILON),
+            ))
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    buckets: HashMap<RpcRequest, Bucket>,
+    counters: RequestCounters,
+}
+
+/// Token buckets of the RPC methods together with the counters of their requests.
+///
+/// Clones share the buckets and the counters, so a clone kept by the caller observes the requests of the client.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+    limits: Arc<RateLimits>,
+    state: Arc<Mutex<LimiterState>>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: RateLimits) -> Self {
+        Self {
+            limits: Arc::new(limits),
+            state: Arc::default(),
+        }
+    }
+
+    pub fn limits(&self) -> &RateLimits {
+        &self.limits
+    }
+
+    /// Returns a snapshot of the counters.
+    pub fn counters(&self) -> RequestCounters {
+        self.state().counters.clone()
+    }
+
+    pub fn reset_counters(&self) {
+        self.state().counters = RequestCounters::default();
+    }
+
+    /// Waits until the method has a token left and counts the request.
+    pub async fn acquire(&self, request: RpcRequest) {
+        let limit = self.limits.limit(request);
+        loop {
+            let wait = {
+                let mut state = self.state();
+                let wait = state
+                    .buckets
+                    .entry(request)
+                    .or_insert_with(|| Bucket::new(limit))
+                    .take(limit, Instant::now());
+                let counters = state.counters.entry(request);
+                match wait {
+                    Some(wait) => counters.delayed += wait,
+                    None => counters.requests += 1,
+                }
+                wait
+            };
+            match wait {
+                Some(wait) => tokio::time::sleep(wait).await,
+                None => return,
+            }
+        }
+    }
+
+    /// Holds the requests of the method back for the given time.
+    pub fn pause(&self, request: RpcRequest, duration: Duration) {
+        let limit = self.limits.limit(request);
+        let until = Instant::now() + duration;
+
+        let mut state = self.state();
+        let bucket = state.buckets.entry(request).or_insert_with(|| Bucket::new(limit));
+        bucket.paused_until = bucket.paused_until.max(Some(until));
+    }
+
+    /// Counts the time the provider made the request wait, or its final rejection, pausing the method on the latter.
+    fn record_rate_limited(&self, request: RpcRequest, waited: Duration, rejected: bool) {
+        if waited.is_zero() && !rejected {
+            return;
+        }
+        {
+            let mut state = self.state();
+            let counters = state.counters.entry(request);
+            counters.rate_limited += 1;
+            counters.delayed += waited;
+        }
+        if rejected {
+            self.pause(request, self.limits.cooldown);
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, LimiterState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// RPC transport passing the requests of another one through a [`RateLimiter`].
+///
+/// The HTTP sender waits out the `Retry-After` of the `429 Too Many Requests` responses itself and retries a few
+/// times, the waits are counted, and once it gives up the method is paused for the cooldown of the limits.
+pub struct RateLimitedSender<S = HttpSender> {
+    sender: S,
+    limiter: RateLimiter,
+}
+
+impl<S> RateLimitedSender<S> {
+    pub fn new(sender: S, limiter: RateLimiter) -> Self {
+        Self { sender, limiter }
+    }
+
+    pub fn limiter(&self) -> &RateLimiter {
+        &self.limiter
+    }
+}
+
+#[async_trait]
+impl<S: RpcSender + Send + Sync> RpcSender for RateLimitedSender<S> {
+    async fn send(&self, request: RpcRequest, params: Value) -> client_error::Result<Value> {
+        self.limiter.acquire(request).await;
+
+        let rate_limited_time = self.sender.get_transport_stats().rate_limited_time;
+        let result = self.sender.send(request, params).await;
+        let waited = self
+            .sender
+            .get_transport_stats()
+            .rate_limited_time
+            .saturating_sub(rate_limited_time);
+
+        let rejected = matches!(&result, Err(error) if is_too_many_requests(error));
+        self.limiter.record_rate_limited(request, waited, rejected);
+        result
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.sender.get_transport_stats()
+    }
+
+    fn url(&self) -> String {
+        self.sender.url()
+    }
+}
+
+fn is_too_many_requests(error: &client_error::ClientError) -> bool {
+    match error.kind() {
+        ClientErrorKind::Reqwest(error) => error.status().map(|status| status.as_u16()) == Some(TOO_MANY_REQUESTS),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_allows_burst_then_waits_for_refill() {
+        let limit = Some(RateLimit::per_second(1).with_burst(2));
+        let mut bucket = Bucket::new(limit);
+        let now = bucket.refilled_at;
+
+        assert_eq!(bucket.take(limit, now), None);
+        assert_eq!(bucket.take(limit, now), None);
+        assert_eq!(bucket.take(limit, now), Some(Duration::from_secs(1)));
+        assert_eq!(
+            bucket.take(limit, now + Duration::from_millis(500)),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(bucket.take(limit, now + Duration::from_secs(1)), None);
+    }
+
+    #[test]
+    fn bucket_refills_up_to_burst() {
+        let limit = Some(RateLimit::per_second(10).with_burst(2));
+        let mut bucket = Bucket::new(limit);
+        let later = bucket.refilled_at + Duration::from_secs(60);
+
+        assert_eq!(bucket.take(limit, later), None);
+        assert_eq!(bucket.take(limit, later), None);
+        assert!(bucket.take(limit, later).is_some());
+    }
+
+    #[test]
+    fn bucket_without_limit_is_only_paused() {
+        let mut bucket = Bucket::new(None);
+        let now = bucket.refilled_at;
+        for _ in 0..100 {
+            assert_eq!(bucket.take(None, now), None);
+        }
+
+        bucket.paused_until = Some(now + Duration::from_secs(2));
+        assert_eq!(bucket.take(None, now), Some(Duration::from_secs(2)));
+        assert_eq!(bucket.take(None, now + Duration::from_secs(2)), None);
+        assert_eq!(bucket.paused_until, None);
+    }
+
+    #[tokio::test]
+    async fn limiter_counts_requests_by_method() {
+        let limiter = RateLimiter::new(RateLimits::new(RateLimit::per_second(100)));
+        limiter.acquire(RpcRequest::GetSlot).await;
+        let earlier = limiter.counters();
+        limiter.acquire(RpcRequest::GetSlot).await;
+        limiter.acquire(RpcRequest::GetBalance).await;
+
+        let counters = limiter.counters();
+        assert_eq!(counters.get(RpcRequest::GetSlot).requests, 2);
+        assert_eq!(counters.requests(), 3);
+
+        let since = counters.since(&earlier);
+        assert_eq!(since.get(RpcRequest::GetSlot).requests, 1);
+        assert_eq!(since.get(RpcRequest::GetBalance).requests, 1);
+
+        limiter.reset_counters();
+        assert_eq!(limiter.counters().requests(), 0);
+    }
+}
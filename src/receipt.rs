@@ -0,0 +1,135 @@
+use std::sync::{Mutex, PoisonError};
+
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::{clock::Slot, commitment_config::CommitmentConfig, signature::Signature};
+pub use solana_transaction_status::{TransactionConfirmationStatus, TransactionStatus};
+use solana_transaction_status::{UiTransactionEncoding, UiTransactionStatusMeta};
+
+use crate::simulation::Simulation;
+
+/// Outcome of a transaction sent by the client, linking the operation of a helper to the chain.
+///
+/// The slot and the confirmation status come with the confirmation of the transaction. The fee, the consumed compute
+/// units and the logs take another request, so they are only fetched by clients created
+/// [`with_receipt_details`](crate::Client::with_receipt_details), and are still missing when the node doesn't serve
+/// the transaction yet. Receipts of simulated transactions carry the units and the logs of the simulation instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionReceipt {
+    pub signature: Signature,
+
+    /// Slot the transaction was processed in, `None` when it was only simulated or sent without a confirmation.
+    pub slot: Option<Slot>,
+    pub confirmation_status: Option<TransactionConfirmationStatus>,
+    pub fee: Option<u64>,
+    pub compute_units: Option<u64>,
+    pub logs: Option<Vec<String>>,
+}
+
+/// Execution details of a processed transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransactionDetails {
+    pub fee: u64,
+    pub compute_units: Option<u64>,
+    pub logs: Option<Vec<String>>,
+}
+
+impl TransactionDetails {
+    /// Config of the request of the transaction, which is only served once it is confirmed.
+    pub fn config(commitment: CommitmentConfig) -> RpcTransactionConfig {
+        let commitment = if commitment.is_at_least_confirmed() {
+            commitment
+        } else {
+            CommitmentConfig::confirmed()
+        };
+
+        RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(commitment),
+            max_supported_transaction_version: Some(0),
+        }
+    }
+}
+
+impl From<UiTransactionStatusMeta> for TransactionDetails {
+    fn from(meta: UiTransactionStatusMeta) -> Self {
+        Self {
+            fee: meta.fee,
+            compute_units: meta.compute_units_consumed.into(),
+            logs: meta.log_messages.into(),
+        }
+    }
+}
+
+impl TransactionReceipt {
+    pub fn confirmed(signature: Signature, status: &TransactionStatus) -> Self {
+        Self {
+            signature,
+            slot: Some(status.slot),
+            confirmation_status: status.confirmation_status.clone(),
+            fee: None,
+            compute_units: None,
+            logs: None,
+        }
+    }
+
+    /// Receipt of a transaction which was sent without waiting for its confirmation.
+    pub fn sent(signature: Signature) -> Self {
+        Self {
+            signature,
+            slot: None,
+            confirmation_status: None,
+            fee: None,
+            compute_units: None,
+            logs: None,
+        }
+    }
+
+    pub fn simulated(simulation: &Simulation) -> Self {
+        Self {
+            signature: simulation.transaction.signatures[0],
+            slot: None,
+            confirmation_status: None,
+            fee: None,
+            compute_units: simulation.units_consumed,
+            logs: Some(simulation.logs.clone()),
+        }
+    }
+
+    pub fn with_details(mut self, details: TransactionDetails) -> Self {
+        self.fee = Some(details.fee);
+        self.compute_units = details.compute_units;
+        self.logs = details.logs;
+        self
+    }
+
+    /// Receipt of a transaction sent by the client, a confirmed one when its status is known.
+    pub(crate) fn assemble(
+        signature: Signature,
+        status: Option<&TransactionStatus>,
+        details: Option<TransactionDetails>,
+    ) -> Self {
+        let receipt = match status {
+            Some(status) => Self::confirmed(signature, status),
+            None => Self::sent(signature),
+        };
+        match details {
+            Some(details) => receipt.with_details(details),
+            None => receipt,
+        }
+    }
+}
+
+/// Receipts recorded by a client created [`with_receipt_log`](crate::Client::with_receipt_log), including those of
+/// the helpers returning the accounts they create rather than a receipt.
+#[derive(Debug, Default)]
+pub struct ReceiptLog(Mutex<Vec<TransactionReceipt>>);
+
+impl ReceiptLog {
+    pub fn record(&self, receipt: TransactionReceipt) {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).push(receipt);
+    }
+
+    pub fn take(&self) -> Vec<TransactionReceipt> {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
@@ -0,0 +1,214 @@
+use std::time::Duration;
+
+use solana_client::{
+    client_error::ClientErrorKind,
+    rpc_config::RpcSendTransactionConfig,
+    rpc_request::{RpcError, RpcResponseErrorData},
+};
+use solana_sdk::transaction::TransactionError;
+
+use crate::ClientError;
+
+/// Interval between the signature status checks while waiting for a confirmation.
+pub(crate) const STATUS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Strategy applied by the client to every transaction it sends.
+///
+/// A transaction is rebroadcast until it is confirmed or its blockhash expires. Failed attempts are retried after
+/// a backoff, checking the signature status first so a transaction which has already landed is never sent twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, `1` disables retries.
+    pub max_attempts: usize,
+
+    /// Delay before the first retry, doubled with every next one up to `max_backoff`.
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+
+    /// Interval of resending a transaction while waiting for its confirmation.
+    pub rebroadcast_interval: Duration,
+
+    /// Re-sign an expired transaction with a fresh blockhash, when the signers are known to the client.
+    pub resign_on_expiry: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            rebroadcast_interval: Duration::from_secs(2),
+            resign_on_expiry: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn with_backoff(mut self, backoff: Duration, max_backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn with_rebroadcast_interval(mut self, rebroadcast_interval: Duration) -> Self {
+        self.rebroadcast_interval = rebroadcast_interval;
+        self
+    }
+
+    pub fn with_resign_on_expiry(mut self, resign_on_expiry: bool) -> Self {
+        self.resign_on_expiry = resign_on_expiry;
+        self
+    }
+
+    /// Delay before the given retry, starting from `1`.
+    pub fn backoff(&self, retry: usize) -> Duration {
+        let factor = 1u32.checked_shl(retry.saturating_sub(1) as u32).unwrap_or(u32::MAX);
+        self.backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+
+    /// Whether another attempt could succeed after the given error.
+    pub fn is_retryable(&self, error: &ClientError) -> bool {
+        is_expired(error) || is_transient(error)
+    }
+
+    /// Decides what follows the given failed attempt, starting from `1`, of a transaction which can be re-signed by
+    /// the client when `resignable`.
+    pub(crate) fn decide(&self, attempt: usize, error: &ClientError, resignable: bool) -> RetryDecision {
+        if attempt >= self.max_attempts || !self.is_retryable(error) {
+            RetryDecision::GiveUp
+        } else if !is_expired(error) {
+            RetryDecision::Retry
+        } else if resignable && self.resign_on_expiry {
+            RetryDecision::Resign
+        } else {
+            RetryDecision::GiveUp
+        }
+    }
+}
+
+/// What the client does after a failed attempt of sending a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RetryDecision {
+    GiveUp,
+
+    /// Sends the same transaction again, after the backoff.
+    Retry,
+
+    /// Signs the transaction with a fresh blockhash, then sends it again after the backoff.
+    Resign,
+}
+
+/// The transaction can't land anymore because its blockhash has expired.
+pub fn is_expired(error: &ClientError) -> bool {
+    match error {
+        ClientError::Expired(_) => true,
+        ClientError::Client(error) => error.get_transaction_error() == Some(TransactionError::BlockhashNotFound),
+        _ => false,
+    }
+}
+
+/// The request failed because of the connection or an unhealthy node rather than the transaction itself.
+pub fn is_transient(error: &ClientError) -> bool {
+    match error {
+        ClientError::Client(error) => matches!(
+            error.kind(),
+            ClientErrorKind::Io(_)
+                | ClientErrorKind::Reqwest(_)
+                | ClientErrorKind::RpcError(RpcError::RpcRequestError(_))
+                | ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                    data: RpcResponseErrorData::NodeUnhealthy { .. },
+                    ..
+                })
+        ),
+        _ => false,
+    }
+}
+
+/// Config of the rebroadcasts, which were already checked by the preflight of the first send.
+pub(crate) fn rebroadcast_config() -> RpcSendTransactionConfig {
+    RpcSendTransactionConfig {
+        skip_preflight: true,
+        ..RpcSendTransactionConfig::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use solana_client::client_error;
+    use solana_sdk::signature::Signature;
+
+    use super::*;
+
+    fn rpc_error(kind: ClientErrorKind) -> ClientError {
+        ClientError::Client(client_error::ClientError::from(kind))
+    }
+
+    fn io_error() -> ClientError {
+        rpc_error(ClientErrorKind::Io(io::ErrorKind::ConnectionReset.into()))
+    }
+
+    fn transaction_error(error: TransactionError) -> ClientError {
+        rpc_error(ClientErrorKind::TransactionError(error))
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let policy = RetryPolicy::default().with_backoff(Duration::from_millis(100), Duration::from_secs(1));
+
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(4), Duration::from_millis(800));
+        assert_eq!(policy.backoff(5), Duration::from_secs(1));
+        assert_eq!(policy.backoff(100), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn connection_errors_are_transient() {
+        let request_error = RpcError::RpcRequestError("connection closed".to_string());
+        let unhealthy = RpcError::RpcResponseError {
+            code: -32005,
+            message: "node is behind".to_string(),
+            data: RpcResponseErrorData::NodeUnhealthy {
+                num_slots_behind: Some(100),
+            },
+        };
+
+        assert!(is_transient(&io_error()));
+        assert!(is_transient(&rpc_error(ClientErrorKind::RpcError(request_error))));
+        assert!(is_transient(&rpc_error(ClientErrorKind::RpcError(unhealthy))));
+        assert!(!is_transient(&transaction_error(TransactionError::AccountInUse)));
+        assert!(!is_transient(&ClientError::MissingPayer));
+    }
+
+    #[test]
+    fn expired_blockhashes_are_detected() {
+        assert!(is_expired(&ClientError::Expired(Signature::default())));
+        assert!(is_expired(&transaction_error(TransactionError::BlockhashNotFound)));
+        assert!(!is_expired(&transaction_error(TransactionError::AccountInUse)));
+        assert!(!is_expired(&io_error()));
+    }
+
+    #[test]
+    fn decides_by_attempts_and_error() {
+        let policy = RetryPolicy::default().with_max_attempts(3);
+        let expired = ClientError::Expired(Signature::default());
+        let permanent = ClientError::MissingPayer;
+        let resigning = policy.with_resign_on_expiry(true);
+
+        assert_eq!(policy.decide(1, &io_error(), false), RetryDecision::Retry);
+        assert_eq!(policy.decide(3, &io_error(), false), RetryDecision::GiveUp);
+        assert_eq!(policy.decide(1, &permanent, true), RetryDecision::GiveUp);
+        assert_eq!(policy.decide(1, &expired, true), RetryDecision::GiveUp);
+        assert_eq!(resigning.decide(1, &expired, true), RetryDecision::Resign);
+        assert_eq!(resigning.decide(1, &expired, false), RetryDecision::GiveUp);
+    }
+}
@@ -0,0 +1,224 @@
+use std::sync::{Mutex, PoisonError};
+
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_client::{
+    rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+    rpc_response::RpcSimulateTransactionResult,
+};
+use solana_sdk::{
+    account::Account,
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    transaction::{TransactionError, VersionedTransaction},
+};
+
+use crate::{error::SplError, receipt::TransactionReceipt, ClientError, ClientResult};
+
+/// Outcome of a transaction simulated instead of being sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simulation {
+    pub transaction: VersionedTransaction,
+    pub error: Option<TransactionError>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    pub account_diffs: Vec<AccountDiff>,
+}
+
+impl Simulation {
+    pub fn new(
+        transaction: VersionedTransaction,
+        result: RpcSimulateTransactionResult,
+        accounts: &[Pubkey],
+        accounts_before: Vec<Option<Account>>,
+    ) -> Self {
+        let accounts_after = result.accounts.unwrap_or_default();
+        let account_diffs = accounts
+            .iter()
+            .zip(accounts_before)
+            .zip(accounts_after)
+            .map(|((pubkey, before), after)| AccountDiff {
+                pubkey: *pubkey,
+                before,
+                after: after.as_ref().and_then(UiAccount::decode),
+            })
+            .filter(|diff| diff.before != diff.after)
+            .collect();
+
+        Self {
+            transaction,
+            error: result.err,
+            logs: result.logs.unwrap_or_default(),
+            units_consumed: result.units_consumed,
+            account_diffs,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns the index of the failed instruction and its decoded program error.
+    pub fn spl_error(&self) -> Option<(u8, SplError)> {
+        self.error
+            .as_ref()
+            .and_then(|error| SplError::from_transaction_error(&self.transaction, error))
+    }
+
+    /// Writable accounts of the transaction, whose state is compared before and after the simulation.
+    ///
+    /// Only the static keys of the message are considered, accounts loaded from lookup tables are not compared.
+    pub fn writable_accounts(transaction: &VersionedTransaction) -> Vec<Pubkey> {
+        let message = &transaction.message;
+        message
+            .static_account_keys()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| message.is_maybe_writable(*index))
+            .map(|(_, pubkey)| *pubkey)
+            .collect()
+    }
+
+    pub fn config(accounts: &[Pubkey], commitment: CommitmentConfig) -> RpcSimulateTransactionConfig {
+        RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            commitment: Some(commitment),
+            accounts: Some(RpcSimulateTransactionAccountsConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                addresses: accounts.iter().map(ToString::to_string).collect(),
+            }),
+            ..RpcSimulateTransactionConfig::default()
+        }
+    }
+}
+
+/// State of an account changed by a simulated transaction, `None` meaning the account does not exist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountDiff {
+    pub pubkey: Pubkey,
+    pub before: Option<Account>,
+    pub after: Option<Account>,
+}
+
+impl AccountDiff {
+    pub fn lamports_change(&self) -> i128 {
+        let lamports = |account: &Option<Account>| account.as_ref().map_or(0, |account| account.lamports as i128);
+        lamports(&self.after) - lamports(&self.before)
+    }
+}
+
+/// Simulations recorded by a client running in the simulation mode.
+#[derive(Debug, Default)]
+pub struct SimulationLog(Mutex<Vec<Simulation>>);
+
+impl SimulationLog {
+    pub fn record(&self, simulation: Simulation) {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).push(simulation);
+    }
+
+    /// Records the simulation, returning the receipt of a successful one and the error of a failed one.
+    pub(crate) fn record_result(&self, simulation: Simulation) -> ClientResult<TransactionReceipt> {
+        self.record(simulation.clone());
+        if simulation.is_ok() {
+            Ok(TransactionReceipt::simulated(&simulation))
+        } else {
+            Err(ClientError::Simulation(Box::new(simulation)))
+        }
+    }
+
+    pub fn take(&self) -> Vec<Simulation> {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{
+        instruction::InstructionError,
+        program_pack::Pack,
+        rent::Rent,
+        signature::{Keypair, Signer},
+    };
+    use spl_token::{
+        error::TokenError,
+        state::{Account as TokenAccount, AccountState},
+    };
+
+    use super::*;
+    use crate::{backend::Backend, mock::MockBackend, Client, SplToken};
+
+    fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+        let mut data = vec![0; TokenAccount::LEN];
+        let state = TokenAccount {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            state: AccountState::Initialized,
+            ..TokenAccount::default()
+        };
+        state.pack_into_slice(&mut data);
+        Account {
+            lamports: Rent::default().minimum_balance(TokenAccount::LEN),
+            data,
+            owner: spl_token::id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn helpers_are_simulated_instead_of_sent() {
+        let owner = Keypair::new();
+        let (mint, source, destination) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let backend = MockBackend::new()
+            .with_account(source, token_account(&mint, &owner.pubkey(), 100))
+            .with_account(destination, token_account(&mint, &owner.pubkey(), 0));
+        let logs = vec![
+            format!("Program {} invoke [1]", spl_token::id()),
+            format!("Program {} success", spl_token::id()),
+        ];
+        let accounts_after = [
+            (source, Some(token_account(&mint, &owner.pubkey(), 60))),
+            (destination, Some(token_account(&mint, &owner.pubkey(), 40))),
+        ];
+        backend.set_simulation(logs.clone(), 3_000, accounts_after);
+        let client = Client::new(backend, Keypair::new()).with_simulation();
+
+        let receipt = client.transfer_to(&owner, &mint, &source, &destination, 40, 2).unwrap();
+
+        assert_eq!(receipt.slot, None);
+        assert_eq!(receipt.compute_units, Some(3_000));
+        assert_eq!(receipt.logs.as_ref(), Some(&logs));
+        let status = client.get_signature_status_with_commitment(&receipt.signature, CommitmentConfig::processed());
+        assert!(status.unwrap().is_none());
+
+        let simulations = client.take_simulations();
+        assert_eq!(simulations.len(), 1);
+        let simulation = &simulations[0];
+        assert!(simulation.is_ok());
+        assert_eq!(simulation.logs, logs);
+        assert_eq!(simulation.units_consumed, Some(3_000));
+
+        let amount = |account: &Option<Account>| TokenAccount::unpack(&account.as_ref().unwrap().data).unwrap().amount;
+        let change = |pubkey: Pubkey| {
+            let diff = simulation.account_diffs.iter().find(|diff| diff.pubkey == pubkey)?;
+            Some((amount(&diff.before), amount(&diff.after)))
+        };
+        assert_eq!(simulation.account_diffs.len(), 2);
+        assert_eq!(change(source), Some((100, 60)));
+        assert_eq!(change(destination), Some((0, 40)));
+    }
+
+    #[test]
+    fn failed_simulation_is_returned() {
+        let client = Client::new(MockBackend::new(), Keypair::new()).with_simulation();
+        client.fail_transaction(0, TransactionError::InstructionError(0, InstructionError::Custom(1)));
+        let (owner, mint) = (Keypair::new(), Pubkey::new_unique());
+
+        let result = client.transfer_to(&owner, &mint, &Pubkey::new_unique(), &Pubkey::new_unique(), 40, 2);
+
+        let error = Some((0, SplError::Token(TokenError::InsufficientFunds)));
+        assert!(matches!(result, Err(ClientError::Simulation(simulation)) if simulation.spl_error() == error));
+        assert_eq!(client.take_simulations().len(), 1);
+    }
+}
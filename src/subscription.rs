@@ -0,0 +1,303 @@
+//! WebSocket subscriptions delivering decoded updates of accounts, swap pools and signatures instead of polling.
+//!
+//! Every subscription keeps a connection to the pubsub endpoint of the cluster, which is derived from the RPC URL
+//! unless set with [`Client::with_websocket_url`], and unsubscribes when dropped:
+//!
+//! ```no_run
+//! use solana_client_helpers::{Client, Subscriptions};
+//! # use solana_sdk::pubkey::Pubkey;
+//! # let (client, token_account): (Client, Pubkey) = unimplemented!();
+//!
+//! let subscription = client.subscribe_token_account(&token_account)?;
+//! for update in subscription.iter() {
+//!     let update = update?;
+//!     println!("slot {}: {} tokens", update.slot, update.value.amount);
+//! }
+//! # Ok::<(), solana_client_helpers::ClientError>(())
+//! ```
+
+use std::{
+    iter,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::{Receiver, Select};
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_client::{
+    client_error,
+    pubsub_client::{PubsubAccountClientSubscription, PubsubClient, PubsubSignatureClientSubscription},
+    rpc_config::{RpcAccountInfoConfig, RpcSignatureSubscribeConfig},
+    rpc_response::{ProcessedSignatureResult, Response as RpcResponse, RpcSignatureResult},
+};
+use solana_sdk::{account::Account, clock::Slot, pubkey::Pubkey, signature::Signature};
+use spl_token::state::Account as TokenAccount;
+use spl_token_swap::state::SwapVersion;
+
+use crate::{backend::Backend, token::unpack_token_state, Client, ClientError, ClientResult};
+
+/// Decoded value of an account together with the slot it was updated in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Update<T> {
+    pub slot: Slot,
+    pub value: T,
+}
+
+/// Subscription to the changes of an account, decoded into `T`.
+pub struct AccountSubscription<T> {
+    pubkey: Pubkey,
+    decode: fn(&Pubkey, &Account) -> ClientResult<T>,
+    receiver: Receiver<RpcResponse<UiAccount>>,
+    _subscription: PubsubAccountClientSubscription,
+}
+
+impl<T> AccountSubscription<T> {
+    pub fn pubkey(&self) -> &Pubkey {
+        &self.pubkey
+    }
+
+    /// Waits for the next update of the account.
+    pub fn recv(&self) -> ClientResult<Update<T>> {
+        let response = self.receiver.recv().map_err(|_| ClientError::SubscriptionClosed)?;
+        self.decode_response(response)
+    }
+
+    /// Waits for the next update of the account for the given time at most.
+    pub fn recv_timeout(&self, timeout: Duration) -> ClientResult<Option<Update<T>>> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(response) => self.decode_response(response).map(Some),
+            Err(error) if error.is_timeout() => Ok(None),
+            Err(_) => Err(ClientError::SubscriptionClosed),
+        }
+    }
+
+    /// Iterates over the updates until the subscription is closed by the cluster.
+    pub fn iter(&self) -> impl Iterator<Item = ClientResult<Update<T>>> + '_ {
+        iter::from_fn(move || match self.recv() {
+            Err(ClientError::SubscriptionClosed) => None,
+            result => Some(result),
+        })
+    }
+
+    fn decode_response(&self, response: RpcResponse<UiAccount>) -> ClientResult<Update<T>> {
+        let account = response
+            .value
+            .decode::<Account>()
+            .ok_or(ClientError::InvalidAccountData(self.pubkey))?;
+        Ok(Update {
+            slot: response.context.slot,
+            value: (self.decode)(&self.pubkey, &account)?,
+        })
+    }
+}
+
+/// Amounts of the tokens held by a swap pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SwapReserves {
+    pub token_a: u64,
+    pub token_b: u64,
+}
+
+/// Subscription to the reserves of a swap pool, updated whenever either of its token accounts changes.
+pub struct SwapSubscription {
+    swap: Pubkey,
+    reserves: SwapReserves,
+    token_a: AccountSubscription<TokenAccount>,
+    token_b: AccountSubscription<TokenAccount>,
+}
+
+impl SwapSubscription {
+    pub fn swap(&self) -> &Pubkey {
+        &self.swap
+    }
+
+    /// Returns the latest known reserves.
+    pub fn reserves(&self) -> SwapReserves {
+        self.reserves
+    }
+
+    /// Waits for the next change of the reserves.
+    pub fn recv(&mut self) -> ClientResult<Update<SwapReserves>> {
+        self.recv_until(None).map(|update| update.expect("no deadline to pass"))
+    }
+
+    /// Waits for the next change of the reserves for the given time at most.
+    pub fn recv_timeout(&mut self, timeout: Duration) -> ClientResult<Option<Update<SwapReserves>>> {
+        self.recv_until(Some(Instant::now() + timeout))
+    }
+
+    /// Iterates over the updates until the subscription is closed by the cluster.
+    pub fn iter(&mut self) -> impl Iterator<Item = ClientResult<Update<SwapReserves>>> + '_ {
+        iter::from_fn(move || match self.recv() {
+            Err(ClientError::SubscriptionClosed) => None,
+            result => Some(result),
+        })
+    }
+
+    fn recv_until(&mut self, deadline: Option<Instant>) -> ClientResult<Option<Update<SwapReserves>>> {
+        let mut select = Select::new();
+        let token_a = select.recv(&self.token_a.receiver);
+        select.recv(&self.token_b.receiver);
+
+        let operation = match deadline {
+            Some(deadline) => match select.select_deadline(deadline) {
+                Ok(operation) => operation,
+                Err(_) => return Ok(None),
+            },
+            None => select.select(),
+        };
+        let is_token_a = operation.index() == token_a;
+        let subscription = if is_token_a { &self.token_a } else { &self.token_b };
+        let response = operation
+            .recv(&subscription.receiver)
+            .map_err(|_| ClientError::SubscriptionClosed)?;
+        let update = subscription.decode_response(response)?;
+
+        if is_token_a {
+            self.reserves.token_a = update.value.amount;
+        } else {
+            self.reserves.token_b = update.value.amount;
+        }
+        Ok(Some(Update {
+            slot: update.slot,
+            value: self.reserves,
+        }))
+    }
+}
+
+/// Subscription to the confirmation of a transaction.
+pub struct SignatureSubscription {
+    signature: Signature,
+    receiver: Receiver<RpcResponse<RpcSignatureResult>>,
+    _subscription: PubsubSignatureClientSubscription,
+}
+
+impl SignatureSubscription {
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// Waits for the transaction to reach the commitment of the client, returning the slot it was processed in.
+    ///
+    /// A transaction which never lands is never notified of, so [`SignatureSubscription::wait_timeout`] is to be
+    /// preferred unless its blockhash is watched otherwise.
+    pub fn wait(&self) -> ClientResult<Slot> {
+        loop {
+            let response = self.receiver.recv().map_err(|_| ClientError::SubscriptionClosed)?;
+            if let Some(slot) = confirmed_slot(response)? {
+                return Ok(slot);
+            }
+        }
+    }
+
+    /// Waits for the transaction to reach the commitment of the client for the given time at most.
+    pub fn wait_timeout(&self, timeout: Duration) -> ClientResult<Option<Slot>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let response = match self.receiver.recv_deadline(deadline) {
+                Ok(response) => response,
+                Err(error) if error.is_timeout() => return Ok(None),
+                Err(_) => return Err(ClientError::SubscriptionClosed),
+            };
+            if let Some(slot) = confirmed_slot(response)? {
+                return Ok(Some(slot));
+            }
+        }
+    }
+}
+
+fn confirmed_slot(response: RpcResponse<RpcSignatureResult>) -> ClientResult<Option<Slot>> {
+    match response.value {
+        RpcSignatureResult::ProcessedSignature(ProcessedSignatureResult { err: Some(error) }) => {
+            Err(client_error::ClientError::from(error).into())
+        },
+        RpcSignatureResult::ProcessedSignature(ProcessedSignatureResult { err: None }) => {
+            Ok(Some(response.context.slot))
+        },
+        RpcSignatureResult::ReceivedSignature(_) => Ok(None),
+    }
+}
+
+pub trait Subscriptions {
+    fn subscribe_account(&self, pubkey: &Pubkey) -> ClientResult<AccountSubscription<Account>>;
+    fn subscribe_token_account(&self, account: &Pubkey) -> ClientResult<AccountSubscription<TokenAccount>>;
+    fn subscribe_swap_reserves(&self, swap: &Pubkey) -> ClientResult<SwapSubscription>;
+    fn subscribe_signature(&self, signature: &Signature) -> ClientResult<SignatureSubscription>;
+}
+
+impl<B: Backend> Subscriptions for Client<B> {
+    fn subscribe_account(&self, pubkey: &Pubkey) -> ClientResult<AccountSubscription<Account>> {
+        self.subscribe_decoded(pubkey, |_, account| Ok(account.clone()))
+    }
+
+    fn subscribe_token_account(&self, account: &Pubkey) -> ClientResult<AccountSubscription<TokenAccount>> {
+        self.subscribe_decoded(account, unpack_token_state)
+    }
+
+    /// Subscribes to the token accounts of the swap pool, starting from their current amounts.
+    fn subscribe_swap_reserves(&self, swap: &Pubkey) -> ClientResult<SwapSubscription> {
+        let swap_account = self
+            .get_account_with_commitment(swap, self.commitment())?
+            .ok_or(ClientError::AccountNotFound(*swap))?;
+        let state = SwapVersion::unpack(&swap_account.data)?;
+        let (token_a, token_b) = (*state.token_a_account(), *state.token_b_account());
+
+        let token_a_subscription = self.subscribe_token_account(&token_a)?;
+        let token_b_subscription = self.subscribe_token_account(&token_b)?;
+
+        let mut amounts = Vec::with_capacity(2);
+        for (pubkey, account) in [token_a, token_b]
+            .iter()
+            .zip(self.get_multiple_accounts(&[token_a, token_b])?)
+        {
+            let account = account.ok_or(ClientError::AccountNotFound(*pubkey))?;
+            amounts.push(unpack_token_state::<TokenAccount>(pubkey, &account)?.amount);
+        }
+
+        Ok(SwapSubscription {
+            swap: *swap,
+            reserves: SwapReserves {
+                token_a: amounts[0],
+                token_b: amounts[1],
+            },
+            token_a: token_a_subscription,
+            token_b: token_b_subscription,
+        })
+    }
+
+    fn subscribe_signature(&self, signature: &Signature) -> ClientResult<SignatureSubscription> {
+        let config = RpcSignatureSubscribeConfig {
+            commitment: Some(self.commitment()),
+            enable_received_notification: Some(false),
+        };
+        let (subscription, receiver) =
+            PubsubClient::signature_subscribe(&self.websocket_url()?, signature, Some(config))?;
+
+        Ok(SignatureSubscription {
+            signature: *signature,
+            receiver,
+            _subscription: subscription,
+        })
+    }
+}
+
+impl<B: Backend> Client<B> {
+    fn subscribe_decoded<T>(
+        &self,
+        pubkey: &Pubkey,
+        decode: fn(&Pubkey, &Account) -> ClientResult<T>,
+    ) -> ClientResult<AccountSubscription<T>> {
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(self.commitment()),
+            ..RpcAccountInfoConfig::default()
+        };
+        let (subscription, receiver) = PubsubClient::account_subscribe(&self.websocket_url()?, pubkey, Some(config))?;
+
+        Ok(AccountSubscription {
+            pubkey: *pubkey,
+            decode,
+            receiver,
+            _subscription: subscription,
+        })
+    }
+}
@@ -2,14 +2,12 @@ use solana_sdk::{
     program_pack::Pack,
     pubkey::Pubkey,
     signature::{Keypair, Signer},
-    system_instruction,
-    transaction::Transaction,
 };
-use spl_token::state::Account as TokenAccount;
+use spl_token::{native_mint, state::Account as TokenAccount};
 pub use spl_token_swap::curve::fees::Fees;
-use spl_token_swap::{curve::base::SwapCurve, instruction::Swap};
+use tracing::instrument;
 
-use crate::{Client, ClientResult, SplToken};
+use crate::{backend::Backend, instructions::swap as instructions, Client, ClientResult, SplToken, TransactionReceipt};
 
 pub struct SwapKeys {
     pub swap: Keypair,
@@ -27,7 +25,7 @@ pub trait SplSwap {
     fn create_swap(
         &self,
         swap_program_id: &Pubkey,
-        swap_account: &Keypair,
+        swap_account: &dyn Signer,
         swap_authority_address: &Pubkey,
         swap_authority_nonce: u8,
         pool_token_mint_address: &Pubkey,
@@ -42,7 +40,7 @@ pub trait SplSwap {
     fn create_swap_and_init(
         &self,
         swap_program_id: &Pubkey,
-        owner: &Keypair,
+        owner: &dyn Signer,
         token_a_mint_address: &Pubkey,
         token_a_maker: Option<impl Fn(&Pubkey) -> ClientResult<Keypair>>,
         token_b_mint_address: &Pubkey,
@@ -58,7 +56,7 @@ pub trait SplSwap {
         swap_program_id: &Pubkey,
         swap_account_address: &Pubkey,
         swap_authority_address: &Pubkey,
-        user_transfer_authority: &Keypair,
+        user_transfer_authority: &dyn Signer,
         source_address: &Pubkey,
         pool_source_address: &Pubkey,
         pool_destination_address: &Pubkey,
@@ -67,14 +65,32 @@ pub trait SplSwap {
         fee_account_address: &Pubkey,
         amount_in: u64,
         minimum_amount_out: u64,
-    ) -> ClientResult<()>;
+    ) -> ClientResult<TransactionReceipt>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn swap_wrapping_sol(
+        &self,
+        swap_program_id: &Pubkey,
+        swap_account_address: &Pubkey,
+        swap_authority_address: &Pubkey,
+        user_transfer_authority: &dyn Signer,
+        source_address: &Pubkey,
+        pool_source_address: &Pubkey,
+        pool_destination_address: &Pubkey,
+        destination_address: &Pubkey,
+        pool_token_mint_address: &Pubkey,
+        fee_account_address: &Pubkey,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> ClientResult<TransactionReceipt>;
 }
 
-impl SplSwap for Client {
+impl<B: Backend> SplSwap for Client<B> {
+    #[instrument(skip_all, fields(swap = %swap_account.pubkey(), pool_mint = %pool_token_mint_address))]
     fn create_swap(
         &self,
         swap_program_id: &Pubkey,
-        swap_account: &Keypair,
+        swap_account: &dyn Signer,
         swap_authority_address: &Pubkey,
         swap_authority_nonce: u8,
         pool_token_mint_address: &Pubkey,
@@ -87,69 +103,36 @@ impl SplSwap for Client {
         let fee_account = Keypair::new();
         let pool_token_initial_supply_account = Keypair::new();
 
-        let mut transaction = Transaction::new_with_payer(
-            &[
-                system_instruction::create_account(
-                    &self.payer_pubkey(),
-                    &fee_account.pubkey(),
-                    self.get_minimum_balance_for_rent_exemption(TokenAccount::LEN)?,
-                    TokenAccount::LEN as u64,
-                    &spl_token::id(),
-                ),
-                spl_token::instruction::initialize_account(
-                    &spl_token::id(),
-                    &fee_account.pubkey(),
-                    pool_token_mint_address,
-                    fee_account_owner_address,
-                )?,
-                system_instruction::create_account(
-                    &self.payer_pubkey(),
-                    &pool_token_initial_supply_account.pubkey(),
-                    self.get_minimum_balance_for_rent_exemption(TokenAccount::LEN)?,
-                    TokenAccount::LEN as u64,
-                    &spl_token::id(),
-                ),
-                spl_token::instruction::initialize_account(
-                    &spl_token::id(),
-                    &pool_token_initial_supply_account.pubkey(),
-                    pool_token_mint_address,
-                    owner_address,
-                )?,
-                spl_token_swap::instruction::initialize(
-                    swap_program_id,
-                    &spl_token::id(),
-                    &swap_account.pubkey(),
-                    swap_authority_address,
-                    token_a_address,
-                    token_b_address,
-                    pool_token_mint_address,
-                    &fee_account.pubkey(),
-                    &pool_token_initial_supply_account.pubkey(),
-                    swap_authority_nonce,
-                    fees,
-                    SwapCurve::default(),
-                )?,
-            ],
-            Some(&self.payer_pubkey()),
-        );
-        transaction.sign(
-            &vec![
-                self.payer(),
-                swap_account,
-                &fee_account,
-                &pool_token_initial_supply_account,
-            ],
-            self.latest_blockhash()?,
-        );
-        self.process_transaction(&transaction)?;
+        let instructions = instructions::create_swap(
+            &self.payer_pubkey(),
+            swap_program_id,
+            &swap_account.pubkey(),
+            swap_authority_address,
+            swap_authority_nonce,
+            pool_token_mint_address,
+            token_a_address,
+            token_b_address,
+            &fee_account.pubkey(),
+            &pool_token_initial_supply_account.pubkey(),
+            owner_address,
+            fees,
+            fee_account_owner_address,
+            self.get_minimum_balance_for_rent_exemption(TokenAccount::LEN)?,
+        )?;
+        self.process_instructions(&instructions.instructions, &[
+            swap_account,
+            &fee_account,
+            &pool_token_initial_supply_account,
+        ])?;
 
         Ok((fee_account, pool_token_initial_supply_account))
     }
 
+    #[instrument(skip_all, fields(mint_a = %token_a_mint_address, mint_b = %token_b_mint_address))]
     fn create_swap_and_init<'a>(
         &self,
         swap_program_id: &Pubkey,
-        owner: &Keypair,
+        owner: &dyn Signer,
         token_a_mint_address: &Pubkey,
         token_a_maker: Option<impl Fn(&Pubkey) -> ClientResult<Keypair>>,
         token_b_mint_address: &Pubkey,
@@ -203,12 +186,21 @@ impl SplSwap for Client {
         })
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            swap = %swap_account_address,
+            pool_mint = %pool_token_mint_address,
+            amount_in = amount_in,
+            minimum_amount_out = minimum_amount_out
+        )
+    )]
     fn swap(
         &self,
         swap_program_id: &Pubkey,
         swap_account_address: &Pubkey,
         swap_authority_address: &Pubkey,
-        user_transfer_authority: &Keypair,
+        user_transfer_authority: &dyn Signer,
         source_address: &Pubkey,
         pool_source_address: &Pubkey,
         pool_destination_address: &Pubkey,
@@ -217,30 +209,293 @@ impl SplSwap for Client {
         fee_account_address: &Pubkey,
         amount_in: u64,
         minimum_amount_out: u64,
-    ) -> ClientResult<()> {
-        let mut transaction = Transaction::new_with_payer(
-            &[spl_token_swap::instruction::swap(
-                swap_program_id,
-                &spl_token::id(),
-                swap_account_address,
-                swap_authority_address,
-                &user_transfer_authority.pubkey(),
-                source_address,
-                pool_source_address,
-                pool_destination_address,
-                destination_address,
-                pool_token_mint_address,
-                fee_account_address,
-                None,
-                Swap {
-                    amount_in,
-                    minimum_amount_out,
-                },
-            )?],
-            Some(&self.payer_pubkey()),
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::swap(
+            swap_program_id,
+            swap_account_address,
+            swap_authority_address,
+            &user_transfer_authority.pubkey(),
+            source_address,
+            pool_source_address,
+            pool_destination_address,
+            destination_address,
+            pool_token_mint_address,
+            fee_account_address,
+            amount_in,
+            minimum_amount_out,
+        )?;
+
+        self.process_instructions(&instructions.instructions, &[user_transfer_authority])
+    }
+
+    /// Same as [`SplSwap::swap`], but swaps SOL of the user through temporary wrapped SOL accounts. When SOL is
+    /// swapped, `amount_in` lamports of the user are wrapped into a new source account in place of `source_address`,
+    /// and when SOL is swapped for, a new destination account receives it in place of `destination_address`. The
+    /// user funds these accounts and gets all of their lamports back when they are closed after the swap, so wrapped
+    /// SOL the user already holds is left alone.
+    #[instrument(
+        skip_all,
+        fields(
+            swap = %swap_account_address,
+            pool_mint = %pool_token_mint_address,
+            amount_in = amount_in,
+            minimum_amount_out = minimum_amount_out
+        )
+    )]
+    fn swap_wrapping_sol(
+        &self,
+        swap_program_id: &Pubkey,
+        swap_account_address: &Pubkey,
+        swap_authority_address: &Pubkey,
+        user_transfer_authority: &dyn Signer,
+        source_address: &Pubkey,
+        pool_source_address: &Pubkey,
+        pool_destination_address: &Pubkey,
+        destination_address: &Pubkey,
+        pool_token_mint_address: &Pubkey,
+        fee_account_address: &Pubkey,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> ClientResult<TransactionReceipt> {
+        let pool_accounts = self.get_multiple_token_accounts(&[*pool_source_address, *pool_destination_address])?;
+        let is_native = |account: &Option<TokenAccount>| {
+            account
+                .as_ref()
+                .map_or(false, |account| account.mint == native_mint::id())
+        };
+        let wrapped_source = is_native(&pool_accounts[0]).then(Keypair::new);
+        let wrapped_destination = is_native(&pool_accounts[1]).then(Keypair::new);
+
+        let instructions = instructions::swap_wrapping_sol(
+            swap_program_id,
+            swap_account_address,
+            swap_authority_address,
+            &user_transfer_authority.pubkey(),
+            &wrapped_source.as_ref().map_or(*source_address, Keypair::pubkey),
+            pool_source_address,
+            pool_destination_address,
+            &wrapped_destination
+                .as_ref()
+                .map_or(*destination_address, Keypair::pubkey),
+            pool_token_mint_address,
+            fee_account_address,
+            amount_in,
+            minimum_amount_out,
+            wrapped_source.is_some(),
+            wrapped_destination.is_some(),
+            self.get_minimum_balance_for_rent_exemption(TokenAccount::LEN)?,
+        )?;
+
+        let mut signers = vec![user_transfer_authority];
+        signers.extend(wrapped_source.iter().map(|keypair| keypair as &dyn Signer));
+        signers.extend(wrapped_destination.iter().map(|keypair| keypair as &dyn Signer));
+        self.process_instructions(&instructions.instructions, &signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{
+        account::Account, instruction::InstructionError, rent::Rent, system_instruction::SystemInstruction,
+        transaction::TransactionError,
+    };
+    use spl_token::{instruction::TokenInstruction, state::AccountState};
+    use spl_token_swap::{
+        error::SwapError,
+        instruction::{Swap, SwapInstruction},
+    };
+
+    use super::*;
+    use crate::{
+        error::SplError,
+        mock::{MockBackend, RecordedInstruction},
+        ClientError,
+    };
+
+    fn token_account(mint: Pubkey) -> Account {
+        let mut data = vec![0; TokenAccount::LEN];
+        TokenAccount {
+            mint,
+            owner: Pubkey::new_unique(),
+            state: AccountState::Initialized,
+            ..TokenAccount::default()
+        }
+        .pack_into_slice(&mut data);
+        Account {
+            lamports: 2_039_280,
+            data,
+            owner: spl_token::id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn create_swap_and_init(
+        client: &Client<MockBackend>,
+        token_a_maker: Option<impl Fn(&Pubkey) -> ClientResult<Keypair>>,
+    ) -> ClientResult<SwapKeys> {
+        client.create_swap_and_init(
+            &spl_token_swap::id(),
+            &Keypair::new(),
+            &Pubkey::new_unique(),
+            token_a_maker,
+            &Pubkey::new_unique(),
+            None::<fn(&Pubkey) -> ClientResult<Keypair>>,
+            6,
+            Fees::default(),
+            &Pubkey::new_unique(),
+        )
+    }
+
+    #[test]
+    fn create_swap_initializes_swap() {
+        let client = Client::new(MockBackend::new(), Keypair::new());
+        client
+            .create_swap(
+                &spl_token_swap::id(),
+                &Keypair::new(),
+                &Pubkey::new_unique(),
+                255,
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                Fees::default(),
+                &Pubkey::new_unique(),
+            )
+            .unwrap();
+
+        let instructions = client.instructions();
+        let count = |matches: fn(&RecordedInstruction) -> bool| instructions.iter().filter(|&i| matches(i)).count();
+        assert_eq!(instructions.len(), 5);
+        assert_eq!(
+            count(|i| matches!(i.system(), Some(SystemInstruction::CreateAccount { .. }))),
+            2
+        );
+        assert_eq!(
+            count(|i| matches!(i.token(), Some(TokenInstruction::InitializeAccount))),
+            2
+        );
+        assert_eq!(count(|i| matches!(i.swap(), Some(SwapInstruction::Initialize(_)))), 1);
+        assert_eq!(client.transactions().len(), 1);
+    }
+
+    #[test]
+    fn create_swap_and_init_stops_at_failed_transaction() {
+        let client = Client::new(MockBackend::new(), Keypair::new());
+        client.fail_transaction(2, TransactionError::InsufficientFundsForFee);
+
+        assert!(create_swap_and_init(&client, None::<fn(&Pubkey) -> ClientResult<Keypair>>).is_err());
+        assert_eq!(client.transactions().len(), 3);
+    }
+
+    #[test]
+    fn create_swap_and_init_fails_with_swap_initialization() {
+        let client = Client::new(MockBackend::new(), Keypair::new());
+        client.fail_transaction(4, TransactionError::InstructionError(4, InstructionError::Custom(0)));
+
+        // The swap initialization follows the creation and initialization of both pool accounts.
+        let result = create_swap_and_init(&client, None::<fn(&Pubkey) -> ClientResult<Keypair>>);
+        assert!(matches!(
+            result,
+            Err(ClientError::Instruction {
+                index: 4,
+                error: SplError::Swap(SwapError::AlreadyInUse),
+                ..
+            })
+        ));
+        assert_eq!(client.transactions().len(), 5);
+    }
+
+    #[test]
+    fn create_swap_and_init_stops_at_failed_account() {
+        let client = Client::new(MockBackend::new(), Keypair::new());
+        let token_a = Pubkey::new_unique();
+        client.fail_account(token_a, "account unavailable");
+
+        let maker = |_: &Pubkey| client.get_token_account(&token_a).map(|_| Keypair::new());
+        assert!(matches!(
+            create_swap_and_init(&client, Some(maker)),
+            Err(ClientError::Client(_))
+        ));
+        assert_eq!(client.transactions().len(), 1);
+    }
+
+    /// Swaps between the pool accounts of the given mints, returning the user and the recorded instructions.
+    fn swap_wrapping_sol(source_mint: Pubkey, destination_mint: Pubkey) -> (Keypair, Vec<RecordedInstruction>) {
+        let (pool_source, pool_destination) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let backend = MockBackend::new()
+            .with_account(pool_source, token_account(source_mint))
+            .with_account(pool_destination, token_account(destination_mint));
+        let client = Client::new(backend, Keypair::new());
+        let user = Keypair::new();
+
+        client
+            .swap_wrapping_sol(
+                &spl_token_swap::id(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &user,
+                &Pubkey::new_unique(),
+                &pool_source,
+                &pool_destination,
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                1_000,
+                900,
+            )
+            .unwrap();
+
+        (user, client.instructions())
+    }
+
+    fn assert_creates_wrapped_account(instructions: &[RecordedInstruction], user: &Pubkey, lamports: u64) -> Pubkey {
+        assert!(matches!(
+            instructions[0].system(),
+            Some(SystemInstruction::CreateAccount { lamports: created, .. }) if created == lamports
+        ));
+        assert_eq!(instructions[0].accounts[0], *user);
+        assert!(matches!(
+            instructions[1].token(),
+            Some(TokenInstruction::InitializeAccount)
+        ));
+        assert_eq!(
+            instructions[1].accounts[..3],
+            [instructions[0].accounts[1], native_mint::id(), *user]
         );
-        transaction.sign(&vec![self.payer(), user_transfer_authority], self.latest_blockhash()?);
+        instructions[0].accounts[1]
+    }
+
+    #[test]
+    fn swap_wrapping_sol_wraps_into_temporary_account() {
+        let (user, instructions) = swap_wrapping_sol(native_mint::id(), Pubkey::new_unique());
+        let rent = Rent::default().minimum_balance(TokenAccount::LEN);
+
+        assert_eq!(instructions.len(), 4);
+        let wrapped = assert_creates_wrapped_account(&instructions, &user.pubkey(), rent + 1_000);
+        assert!(matches!(
+            instructions[2].swap(),
+            Some(SwapInstruction::Swap(Swap {
+                amount_in: 1_000,
+                minimum_amount_out: 900
+            }))
+        ));
+        assert_eq!(instructions[2].accounts[3], wrapped);
+        assert!(matches!(instructions[3].token(), Some(TokenInstruction::CloseAccount)));
+        assert_eq!(instructions[3].accounts, [wrapped, user.pubkey(), user.pubkey()]);
+    }
+
+    #[test]
+    fn swap_wrapping_sol_unwraps_from_temporary_account() {
+        let (user, instructions) = swap_wrapping_sol(Pubkey::new_unique(), native_mint::id());
+        let rent = Rent::default().minimum_balance(TokenAccount::LEN);
 
-        self.process_transaction(&transaction)
+        assert_eq!(instructions.len(), 4);
+        let wrapped = assert_creates_wrapped_account(&instructions, &user.pubkey(), rent);
+        assert!(matches!(instructions[2].swap(), Some(SwapInstruction::Swap(_))));
+        assert_eq!(instructions[2].accounts[6], wrapped);
+        assert!(matches!(instructions[3].token(), Some(TokenInstruction::CloseAccount)));
+        assert_eq!(instructions[3].accounts, [wrapped, user.pubkey(), user.pubkey()]);
     }
 }
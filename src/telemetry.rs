@@ -0,0 +1,103 @@
+//! Tracing spans and metrics of the transactions sent by the helpers.
+//!
+//! Every helper of [`SplToken`](crate::SplToken) and [`SplSwap`](crate::SplSwap), as well as their async
+//! counterparts, runs in a span named after it, which carries the pubkeys of the mints and the pools involved. Each
+//! transaction sent within the helper gets a `transaction` span of its own, recording its signature, the number of
+//! retries and the fee paid. The fee is only known to clients created
+//! [`with_receipt_details`](crate::Client::with_receipt_details), which fetch it with the receipt.
+//!
+//! With the `metrics` feature the transactions are also reported through the [`metrics`](https://docs.rs/metrics)
+//! facade, labeled by the `helper` they were sent by and their `result`:
+//!
+//! - `solana_client_helpers_transactions_total` counter,
+//! - `solana_client_helpers_transaction_duration_seconds` histogram,
+//! - `solana_client_helpers_transaction_retries` histogram,
+//! - `solana_client_helpers_transaction_fee_lamports` histogram.
+//!
+//! The helper label is taken from the span the transaction is sent in, so it is `unknown` unless a tracing
+//! subscriber is installed.
+
+use std::time::Instant;
+
+use solana_sdk::transaction::VersionedTransaction;
+use tracing::{debug, field, info_span, warn, Span};
+
+use crate::ClientResult;
+
+/// Name of the helper of transactions sent outside of any span.
+const UNKNOWN_HELPER: &str = "unknown";
+
+/// Span and measurements of a transaction from its first send until it is confirmed or given up on.
+pub(crate) struct TransactionTelemetry {
+    span: Span,
+    helper: &'static str,
+    started_at: Instant,
+    retries: usize,
+}
+
+impl TransactionTelemetry {
+    pub(crate) fn start(transaction: &VersionedTransaction) -> Self {
+        let helper = Span::current()
+            .metadata()
+            .map_or(UNKNOWN_HELPER, |metadata| metadata.name());
+        let span = info_span!(
+            "transaction",
+            signature = field::display(transaction.signatures[0]),
+            retries = 0,
+            fee = field::Empty
+        );
+
+        Self {
+            span,
+            helper,
+            started_at: Instant::now(),
+            retries: 0,
+        }
+    }
+
+    pub(crate) fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Counts a retry of the transaction, which may have been re-signed with a new signature.
+    pub(crate) fn retried(&mut self, transaction: &VersionedTransaction) {
+        self.retries += 1;
+        self.span.record("retries", &self.retries);
+        self.span
+            .record("signature", &field::display(transaction.signatures[0]));
+    }
+
+    pub(crate) fn finish<T>(self, result: &ClientResult<T>, fee: Option<u64>) {
+        let elapsed = self.started_at.elapsed();
+        if let Some(fee) = fee {
+            self.span.record("fee", &fee);
+        }
+
+        self.span.in_scope(|| match result {
+            Ok(_) => debug!(helper = self.helper, ?elapsed, "transaction confirmed"),
+            Err(error) => warn!(helper = self.helper, ?elapsed, %error, "transaction failed"),
+        });
+
+        #[cfg(feature = "metrics")]
+        {
+            let labels = [
+                ("helper", self.helper.to_string()),
+                ("result", if result.is_ok() { "ok" } else { "error" }.to_string()),
+            ];
+            metrics::counter!("solana_client_helpers_transactions_total", 1, &labels);
+            metrics::histogram!(
+                "solana_client_helpers_transaction_duration_seconds",
+                elapsed.as_secs_f64(),
+                &labels
+            );
+            metrics::histogram!(
+                "solana_client_helpers_transaction_retries",
+                self.retries as f64,
+                &labels
+            );
+            if let Some(fee) = fee {
+                metrics::histogram!("solana_client_helpers_transaction_fee_lamports", fee as f64, &labels);
+            }
+        }
+    }
+}
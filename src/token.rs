@@ -1,16 +1,52 @@
+use solana_client::rpc_request::MAX_MULTIPLE_ACCOUNTS;
 use solana_sdk::{
-    program_pack::Pack,
+    account::Account,
+    instruction::Instruction,
+    program_pack::{IsInitialized, Pack},
     pubkey::Pubkey,
     signature::{Keypair, Signer},
-    system_instruction,
-    transaction::Transaction,
 };
-use spl_token::state::{Account as TokenAccount, Mint};
+pub use spl_token::instruction::AuthorityType;
+use spl_token::{
+    native_mint,
+    state::{Account as TokenAccount, Mint, Multisig},
+};
+use tracing::instrument;
 
 use super::client::{Client, ClientResult};
+use crate::{backend::Backend, instructions::token as instructions, ClientError, TokenAmount, TransactionReceipt};
+
+/// Decodes the state of an account of the token program, checking its owner and that it is initialized.
+pub fn unpack_token_state<T: Pack + IsInitialized>(pubkey: &Pubkey, account: &Account) -> ClientResult<T> {
+    if account.owner != spl_token::id() {
+        return Err(ClientError::InvalidOwner {
+            account: *pubkey,
+            owner: account.owner,
+            expected: spl_token::id(),
+        });
+    }
+    let state = T::unpack_unchecked(&account.data).map_err(|_| ClientError::InvalidAccountData(*pubkey))?;
+    if !state.is_initialized() {
+        return Err(ClientError::UninitializedAccount(*pubkey));
+    }
+
+    Ok(state)
+}
 
 pub trait SplToken {
+    fn get_mint(&self, token_mint: &Pubkey) -> ClientResult<Mint>;
+    fn get_token_account(&self, account: &Pubkey) -> ClientResult<TokenAccount>;
+    fn get_multisig(&self, multisig: &Pubkey) -> ClientResult<Multisig>;
+    fn get_multiple_token_accounts(&self, accounts: &[Pubkey]) -> ClientResult<Vec<Option<TokenAccount>>>;
+    fn get_mint_decimals(&self, token_mint: &Pubkey) -> ClientResult<u8>;
     fn create_token_mint(&self, owner: &Pubkey, decimals: u8) -> ClientResult<Keypair>;
+    fn create_token_mint_with_freeze_authority(
+        &self,
+        owner: &Pubkey,
+        freeze_authority: Option<&Pubkey>,
+        decimals: u8,
+    ) -> ClientResult<Keypair>;
+    fn create_multisig(&self, signers: &[&Pubkey], m: u8) -> ClientResult<Keypair>;
     fn create_token_account(&self, owner: &Pubkey, token_mint: &Pubkey) -> ClientResult<Keypair>;
     fn create_token_account_with_lamports(
         &self,
@@ -20,56 +56,192 @@ pub trait SplToken {
     ) -> ClientResult<Keypair>;
     fn mint_to(
         &self,
-        owner: &Keypair,
+        owner: &dyn Signer,
         token_mint: &Pubkey,
         account: &Pubkey,
         amount: u64,
         decimals: u8,
-    ) -> ClientResult<()>;
+    ) -> ClientResult<TransactionReceipt>;
     fn transfer_to(
         &self,
-        owner: &Keypair,
+        owner: &dyn Signer,
         token_mint: &Pubkey,
         source: &Pubkey,
         destination: &Pubkey,
         amount: u64,
         decimals: u8,
-    ) -> ClientResult<()>;
+    ) -> ClientResult<TransactionReceipt>;
+    fn mint_amount_to(
+        &self,
+        owner: &dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: TokenAmount,
+    ) -> ClientResult<TransactionReceipt>;
+    fn transfer_amount_to(
+        &self,
+        owner: &dyn Signer,
+        token_mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        amount: TokenAmount,
+    ) -> ClientResult<TransactionReceipt>;
+    fn approve(
+        &self,
+        owner: &dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        delegate: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt>;
+    fn revoke(&self, owner: &dyn Signer, account: &Pubkey) -> ClientResult<TransactionReceipt>;
+    fn burn(
+        &self,
+        authority: &dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt>;
+    fn freeze_account(
+        &self,
+        freeze_authority: &dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+    fn thaw_account(
+        &self,
+        freeze_authority: &dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+    fn set_authority(
+        &self,
+        authority: &dyn Signer,
+        owned: &Pubkey,
+        authority_type: AuthorityType,
+        new_authority: Option<&Pubkey>,
+    ) -> ClientResult<TransactionReceipt>;
     fn get_associated_token_address(wallet_address: &Pubkey, token_mint: &Pubkey) -> Pubkey;
     fn create_associated_token_account(
         &self,
-        funder: &Keypair,
+        funder: &dyn Signer,
         recipient: &Pubkey,
         token_mint: &Pubkey,
     ) -> ClientResult<Pubkey>;
     fn create_associated_token_account_by_payer(&self, recipient: &Pubkey, token_mint: &Pubkey)
         -> ClientResult<Pubkey>;
-    fn close_token_account(&self, owner: &Keypair, account: &Pubkey, destination: &Pubkey) -> ClientResult<()>;
+    fn close_token_account(
+        &self,
+        owner: &dyn Signer,
+        account: &Pubkey,
+        destination: &Pubkey,
+    ) -> ClientResult<TransactionReceipt>;
+    fn wrap_sol(&self, owner: &dyn Signer, lamports: u64) -> ClientResult<TransactionReceipt>;
+    fn unwrap_sol(&self, owner: &dyn Signer) -> ClientResult<TransactionReceipt>;
+    fn sync_native(&self, account: &Pubkey) -> ClientResult<TransactionReceipt>;
 }
 
-impl SplToken for Client {
+impl<B: Backend> SplToken for Client<B> {
+    #[instrument(skip_all, fields(mint = %token_mint))]
+    fn get_mint(&self, token_mint: &Pubkey) -> ClientResult<Mint> {
+        let account = self
+            .get_account_with_commitment(token_mint, self.commitment())?
+            .ok_or(ClientError::AccountNotFound(*token_mint))?;
+        unpack_token_state(token_mint, &account)
+    }
+
+    #[instrument(skip_all, fields(account = %account))]
+    fn get_token_account(&self, account: &Pubkey) -> ClientResult<TokenAccount> {
+        let token_account = self
+            .get_account_with_commitment(account, self.commitment())?
+            .ok_or(ClientError::AccountNotFound(*account))?;
+        unpack_token_state(account, &token_account)
+    }
+
+    #[instrument(skip_all, fields(multisig = %multisig))]
+    fn get_multisig(&self, multisig: &Pubkey) -> ClientResult<Multisig> {
+        let account = self
+            .get_account_with_commitment(multisig, self.commitment())?
+            .ok_or(ClientError::AccountNotFound(*multisig))?;
+        unpack_token_state(multisig, &account)
+    }
+
+    /// Fetches the token accounts in batches of [`MAX_MULTIPLE_ACCOUNTS`], `None` for the missing ones.
+    #[instrument(skip_all, fields(accounts = accounts.len()))]
+    fn get_multiple_token_accounts(&self, accounts: &[Pubkey]) -> ClientResult<Vec<Option<TokenAccount>>> {
+        let mut token_accounts = Vec::with_capacity(accounts.len());
+        for pubkeys in accounts.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            for (pubkey, account) in pubkeys.iter().zip(self.get_multiple_accounts(pubkeys)?) {
+                token_accounts.push(
+                    account
+                        .map(|account| unpack_token_state(pubkey, &account))
+                        .transpose()?,
+                );
+            }
+        }
+
+        Ok(token_accounts)
+    }
+
+    /// Returns the decimals of the mint, which are fetched once per client.
+    #[instrument(skip_all, fields(mint = %token_mint))]
+    fn get_mint_decimals(&self, token_mint: &Pubkey) -> ClientResult<u8> {
+        if let Some(decimals) = self.decimals_cache.get(token_mint) {
+            return Ok(decimals);
+        }
+
+        let decimals = self.get_mint(token_mint)?.decimals;
+        self.decimals_cache.insert(*token_mint, decimals);
+        Ok(decimals)
+    }
+
+    #[instrument(skip_all, fields(owner = %owner, decimals = decimals))]
     fn create_token_mint(&self, owner: &Pubkey, decimals: u8) -> ClientResult<Keypair> {
+        self.create_token_mint_with_freeze_authority(owner, None, decimals)
+    }
+
+    #[instrument(skip_all, fields(owner = %owner, decimals = decimals))]
+    fn create_token_mint_with_freeze_authority(
+        &self,
+        owner: &Pubkey,
+        freeze_authority: Option<&Pubkey>,
+        decimals: u8,
+    ) -> ClientResult<Keypair> {
         let token_mint = Keypair::new();
 
-        let mut transaction = Transaction::new_with_payer(
-            &[
-                system_instruction::create_account(
-                    &self.payer_pubkey(),
-                    &token_mint.pubkey(),
-                    self.get_minimum_balance_for_rent_exemption(Mint::LEN)?,
-                    Mint::LEN as u64,
-                    &spl_token::id(),
-                ),
-                spl_token::instruction::initialize_mint(&spl_token::id(), &token_mint.pubkey(), owner, None, decimals)?,
-            ],
-            Some(&self.payer_pubkey()),
-        );
-        transaction.sign(&[self.payer(), &token_mint], self.latest_blockhash()?);
-        self.process_transaction(&transaction)?;
+        let instructions = instructions::create_token_mint_with_freeze_authority(
+            &self.payer_pubkey(),
+            &token_mint.pubkey(),
+            owner,
+            freeze_authority,
+            decimals,
+            self.get_minimum_balance_for_rent_exemption(Mint::LEN)?,
+        )?;
+        self.process_instructions(&instructions.instructions, &[&token_mint])?;
 
         Ok(token_mint)
     }
 
+    /// Creates an `m` of `signers` multisig account, which can be used as the authority of mints and token accounts.
+    #[instrument(skip_all, fields(signers = signers.len(), m = m))]
+    fn create_multisig(&self, signers: &[&Pubkey], m: u8) -> ClientResult<Keypair> {
+        let multisig = Keypair::new();
+
+        let instructions = instructions::create_multisig(
+            &self.payer_pubkey(),
+            &multisig.pubkey(),
+            signers,
+            m,
+            self.get_minimum_balance_for_rent_exemption(Multisig::LEN)?,
+        )?;
+        self.process_instructions(&instructions.instructions, &[&multisig])?;
+
+        Ok(multisig)
+    }
+
+    #[instrument(skip_all, fields(owner = %owner, mint = %token_mint))]
     fn create_token_account(&self, owner: &Pubkey, token_mint: &Pubkey) -> ClientResult<Keypair> {
         self.create_token_account_with_lamports(
             owner,
@@ -78,6 +250,7 @@ impl SplToken for Client {
         )
     }
 
+    #[instrument(skip_all, fields(owner = %owner, mint = %token_mint, lamports = lamports))]
     fn create_token_account_with_lamports(
         &self,
         owner: &Pubkey,
@@ -86,108 +259,174 @@ impl SplToken for Client {
     ) -> ClientResult<Keypair> {
         let token_account = Keypair::new();
 
-        let mut transaction = Transaction::new_with_payer(
-            &[
-                system_instruction::create_account(
-                    &self.payer_pubkey(),
-                    &token_account.pubkey(),
-                    lamports,
-                    TokenAccount::LEN as u64,
-                    &spl_token::id(),
-                ),
-                spl_token::instruction::initialize_account(
-                    &spl_token::id(),
-                    &token_account.pubkey(),
-                    token_mint,
-                    owner,
-                )?,
-            ],
-            Some(&self.payer_pubkey()),
-        );
-        transaction.sign(&[self.payer(), &token_account], self.latest_blockhash()?);
-        self.process_transaction(&transaction)?;
+        let instructions = instructions::create_token_account(
+            &self.payer_pubkey(),
+            &token_account.pubkey(),
+            owner,
+            token_mint,
+            lamports,
+        )?;
+        self.process_instructions(&instructions.instructions, &[&token_account])?;
 
         Ok(token_account)
     }
 
+    #[instrument(skip_all, fields(mint = %token_mint, account = %account, amount = amount))]
     fn mint_to(
         &self,
-        owner: &Keypair,
+        owner: &dyn Signer,
         token_mint: &Pubkey,
         account: &Pubkey,
         amount: u64,
         decimals: u8,
-    ) -> ClientResult<()> {
-        let mut transaction = Transaction::new_with_payer(
-            &[spl_token::instruction::mint_to_checked(
-                &spl_token::id(),
-                token_mint,
-                account,
-                &owner.pubkey(),
-                &[],
-                amount,
-                decimals,
-            )?],
-            Some(&self.payer_pubkey()),
-        );
-        transaction.sign(&[self.payer(), owner], self.latest_blockhash()?);
-        self.process_transaction(&transaction)
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::mint_to(&owner.pubkey(), &[], token_mint, account, amount, decimals)?;
+        self.process_instructions(&instructions.instructions, &[owner])
     }
 
+    #[instrument(skip_all, fields(mint = %token_mint, source = %source, destination = %destination, amount = amount))]
     fn transfer_to(
         &self,
-        authority: &Keypair,
+        authority: &dyn Signer,
         token_mint: &Pubkey,
         source: &Pubkey,
         destination: &Pubkey,
         amount: u64,
         decimals: u8,
-    ) -> ClientResult<()> {
-        let mut transaction = Transaction::new_with_payer(
-            &[spl_token::instruction::transfer_checked(
-                &spl_token::id(),
-                source,
-                token_mint,
-                destination,
-                &authority.pubkey(),
-                &[],
-                amount,
-                decimals,
-            )?],
-            Some(&self.payer_pubkey()),
-        );
-        transaction.sign(&[self.payer(), authority], self.latest_blockhash()?);
-        self.process_transaction(&transaction)
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::transfer_to(
+            &authority.pubkey(),
+            &[],
+            token_mint,
+            source,
+            destination,
+            amount,
+            decimals,
+        )?;
+        self.process_instructions(&instructions.instructions, &[authority])
+    }
+
+    /// Same as [`SplToken::mint_to`], but looks the decimals of the mint up.
+    #[instrument(skip_all, fields(mint = %token_mint, account = %account, amount = ?amount))]
+    fn mint_amount_to(
+        &self,
+        owner: &dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: TokenAmount,
+    ) -> ClientResult<TransactionReceipt> {
+        let decimals = self.get_mint_decimals(token_mint)?;
+        self.mint_to(owner, token_mint, account, amount.to_units(decimals)?, decimals)
+    }
+
+    /// Same as [`SplToken::transfer_to`], but looks the decimals of the mint up.
+    #[instrument(skip_all, fields(mint = %token_mint, source = %source, destination = %destination, amount = ?amount))]
+    fn transfer_amount_to(
+        &self,
+        authority: &dyn Signer,
+        token_mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        amount: TokenAmount,
+    ) -> ClientResult<TransactionReceipt> {
+        let decimals = self.get_mint_decimals(token_mint)?;
+        self.transfer_to(
+            authority,
+            token_mint,
+            source,
+            destination,
+            amount.to_units(decimals)?,
+            decimals,
+        )
+    }
+
+    #[instrument(skip_all, fields(mint = %token_mint, account = %account, delegate = %delegate, amount = amount))]
+    fn approve(
+        &self,
+        owner: &dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        delegate: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions =
+            instructions::approve(&owner.pubkey(), &[], token_mint, account, delegate, amount, decimals)?;
+        self.process_instructions(&instructions.instructions, &[owner])
+    }
+
+    #[instrument(skip_all, fields(account = %account))]
+    fn revoke(&self, owner: &dyn Signer, account: &Pubkey) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::revoke(&owner.pubkey(), &[], account)?;
+        self.process_instructions(&instructions.instructions, &[owner])
+    }
+
+    #[instrument(skip_all, fields(mint = %token_mint, account = %account, amount = amount))]
+    fn burn(
+        &self,
+        authority: &dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::burn(&authority.pubkey(), &[], token_mint, account, amount, decimals)?;
+        self.process_instructions(&instructions.instructions, &[authority])
+    }
+
+    #[instrument(skip_all, fields(mint = %token_mint, account = %account))]
+    fn freeze_account(
+        &self,
+        freeze_authority: &dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::freeze_account(&freeze_authority.pubkey(), &[], token_mint, account)?;
+        self.process_instructions(&instructions.instructions, &[freeze_authority])
+    }
+
+    #[instrument(skip_all, fields(mint = %token_mint, account = %account))]
+    fn thaw_account(
+        &self,
+        freeze_authority: &dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::thaw_account(&freeze_authority.pubkey(), &[], token_mint, account)?;
+        self.process_instructions(&instructions.instructions, &[freeze_authority])
+    }
+
+    /// Changes an authority of a mint or a token account, `None` removing it for good.
+    #[instrument(skip_all, fields(owned = %owned, authority_type = ?authority_type))]
+    fn set_authority(
+        &self,
+        authority: &dyn Signer,
+        owned: &Pubkey,
+        authority_type: AuthorityType,
+        new_authority: Option<&Pubkey>,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::set_authority(&authority.pubkey(), &[], owned, authority_type, new_authority)?;
+        self.process_instructions(&instructions.instructions, &[authority])
     }
 
     fn get_associated_token_address(wallet_address: &Pubkey, token_mint: &Pubkey) -> Pubkey {
         spl_associated_token_account::get_associated_token_address(wallet_address, token_mint)
     }
 
+    #[instrument(skip_all, fields(recipient = %recipient, mint = %token_mint))]
     fn create_associated_token_account(
         &self,
-        funder: &Keypair,
+        funder: &dyn Signer,
         recipient: &Pubkey,
         token_mint: &Pubkey,
     ) -> ClientResult<Pubkey> {
-        let mut transaction = Transaction::new_with_payer(
-            &[spl_associated_token_account::create_associated_token_account(
-                &funder.pubkey(),
-                recipient,
-                token_mint,
-            )],
-            Some(&self.payer_pubkey()),
-        );
-        if funder.pubkey() == self.payer_pubkey() {
-            transaction.sign(&[self.payer()], self.latest_blockhash()?);
-        } else {
-            transaction.sign(&[self.payer(), funder], self.latest_blockhash()?);
-        };
-        self.process_transaction(&transaction)?;
+        let instructions = instructions::create_associated_token_account(&funder.pubkey(), recipient, token_mint);
+        self.process_instructions(&instructions.instructions, &[funder])?;
 
         Ok(Self::get_associated_token_address(recipient, token_mint))
     }
 
+    #[instrument(skip_all, fields(recipient = %recipient, mint = %token_mint))]
     fn create_associated_token_account_by_payer(
         &self,
         recipient: &Pubkey,
@@ -196,18 +435,218 @@ impl SplToken for Client {
         self.create_associated_token_account(self.payer(), recipient, token_mint)
     }
 
-    fn close_token_account(&self, owner: &Keypair, account: &Pubkey, destination: &Pubkey) -> ClientResult<()> {
-        let mut transaction = Transaction::new_with_payer(
-            &[spl_token::instruction::close_account(
-                &spl_token::id(),
-                account,
-                destination,
-                &owner.pubkey(),
-                &[],
-            )?],
-            Some(&self.payer_pubkey()),
-        );
-        transaction.sign(&[self.payer(), owner], self.latest_blockhash()?);
-        self.process_transaction(&transaction)
+    #[instrument(skip_all, fields(account = %account, destination = %destination))]
+    fn close_token_account(
+        &self,
+        owner: &dyn Signer,
+        account: &Pubkey,
+        destination: &Pubkey,
+    ) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::close_token_account(&owner.pubkey(), &[], account, destination)?;
+        self.process_instructions(&instructions.instructions, &[owner])
+    }
+
+    /// Wraps lamports of the owner into their associated token account of the native mint, creating it if needed.
+    #[instrument(skip_all, fields(owner = %owner.pubkey(), lamports = lamports))]
+    fn wrap_sol(&self, owner: &dyn Signer, lamports: u64) -> ClientResult<TransactionReceipt> {
+        let account = Self::get_associated_token_address(&owner.pubkey(), &native_mint::id());
+        let create = self.create_native_account_instructions(&owner.pubkey(), &account)?;
+        let wrap = instructions::wrap_sol(&owner.pubkey(), &account, lamports)?;
+        self.process_instructions(&[create, wrap.instructions].concat(), &[owner])
+    }
+
+    /// Unwraps the wrapped SOL of the owner by closing their associated token account of the native mint.
+    #[instrument(skip_all, fields(owner = %owner.pubkey()))]
+    fn unwrap_sol(&self, owner: &dyn Signer) -> ClientResult<TransactionReceipt> {
+        let account = Self::get_associated_token_address(&owner.pubkey(), &native_mint::id());
+        let instructions = instructions::unwrap_sol(&owner.pubkey(), &[], &account, &owner.pubkey())?;
+        self.process_instructions(&instructions.instructions, &[owner])
+    }
+
+    #[instrument(skip_all, fields(account = %account))]
+    fn sync_native(&self, account: &Pubkey) -> ClientResult<TransactionReceipt> {
+        let instructions = instructions::sync_native(account)?;
+        self.process_instructions(&instructions.instructions, &[])
+    }
+}
+
+impl<B: Backend> Client<B> {
+    /// Returns the instructions creating the token account of the native mint, none if it exists already. Only the
+    /// associated token account of the owner is created, any other missing account fails.
+    pub(crate) fn create_native_account_instructions(
+        &self,
+        owner: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<Vec<Instruction>> {
+        if self.get_account_with_commitment(account, self.commitment())?.is_some() {
+            return Ok(Vec::new());
+        }
+        if *account != Self::get_associated_token_address(owner, &native_mint::id()) {
+            return Err(ClientError::AccountNotFound(*account));
+        }
+
+        Ok(instructions::create_associated_token_account(&self.payer_pubkey(), owner, &native_mint::id()).instructions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{rent::Rent, system_program};
+    use spl_token::state::AccountState;
+
+    use super::*;
+    use crate::{mock::MockBackend, receipt::TransactionDetails};
+
+    fn program_account(owner: Pubkey, data: Vec<u8>) -> Account {
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn packed<T: Pack>(state: T) -> Vec<u8> {
+        let mut data = vec![0; T::LEN];
+        state.pack_into_slice(&mut data);
+        data
+    }
+
+    fn mint(decimals: u8) -> Account {
+        let state = Mint {
+            decimals,
+            is_initialized: true,
+            ..Mint::default()
+        };
+        program_account(spl_token::id(), packed(state))
+    }
+
+    fn token_account(mint: &Pubkey, amount: u64) -> Account {
+        let state = TokenAccount {
+            mint: *mint,
+            owner: Pubkey::new_unique(),
+            amount,
+            state: AccountState::Initialized,
+            ..TokenAccount::default()
+        };
+        program_account(spl_token::id(), packed(state))
+    }
+
+    #[test]
+    fn fetchers_decode_token_state() {
+        let (token_mint, account) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let backend = MockBackend::new()
+            .with_account(token_mint, mint(6))
+            .with_account(account, token_account(&token_mint, 100));
+        let client = Client::new(backend, Keypair::new());
+
+        assert_eq!(client.get_mint(&token_mint).unwrap().decimals, 6);
+        assert_eq!(client.get_token_account(&account).unwrap().amount, 100);
+        assert!(matches!(
+            client.get_mint(&Pubkey::new_unique()),
+            Err(ClientError::AccountNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn fetchers_reject_accounts_of_other_programs() {
+        let (token_mint, account) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let foreign = |account: Account| program_account(system_program::id(), account.data);
+        let backend = MockBackend::new()
+            .with_account(token_mint, foreign(mint(6)))
+            .with_account(account, foreign(token_account(&token_mint, 100)));
+        let client = Client::new(backend, Keypair::new());
+
+        let invalid_owner = |result: ClientResult<_>, pubkey: Pubkey| {
+            matches!(
+                result,
+                Err(ClientError::InvalidOwner { account, owner, expected })
+                    if account == pubkey && owner == system_program::id() && expected == spl_token::id()
+            )
+        };
+        assert!(invalid_owner(client.get_mint(&token_mint).map(drop), token_mint));
+        assert!(invalid_owner(client.get_token_account(&account).map(drop), account));
+    }
+
+    #[test]
+    fn fetchers_reject_uninitialized_accounts() {
+        let (token_mint, account) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let backend = MockBackend::new()
+            .with_account(token_mint, program_account(spl_token::id(), vec![0; Mint::LEN]))
+            .with_account(account, program_account(spl_token::id(), vec![0; TokenAccount::LEN]));
+        let client = Client::new(backend, Keypair::new());
+
+        assert!(matches!(
+            client.get_mint(&token_mint),
+            Err(ClientError::UninitializedAccount(pubkey)) if pubkey == token_mint
+        ));
+        assert!(matches!(
+            client.get_token_account(&account),
+            Err(ClientError::UninitializedAccount(pubkey)) if pubkey == account
+        ));
+    }
+
+    #[test]
+    fn fetchers_reject_malformed_accounts() {
+        let (token_mint, account) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let backend = MockBackend::new()
+            .with_account(token_mint, token_account(&Pubkey::new_unique(), 100))
+            .with_account(account, program_account(spl_token::id(), vec![1; 3]));
+        let client = Client::new(backend, Keypair::new());
+
+        assert!(matches!(
+            client.get_mint(&token_mint),
+            Err(ClientError::InvalidAccountData(pubkey)) if pubkey == token_mint
+        ));
+        assert!(matches!(
+            client.get_token_account(&account),
+            Err(ClientError::InvalidAccountData(pubkey)) if pubkey == account
+        ));
+    }
+
+    #[test]
+    fn missing_token_accounts_are_none() {
+        let token_mint = Pubkey::new_unique();
+        let accounts = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let backend = MockBackend::new()
+            .with_account(accounts[0], token_account(&token_mint, 100))
+            .with_account(accounts[2], token_account(&token_mint, 200));
+        let client = Client::new(backend, Keypair::new());
+
+        let amounts: Vec<_> = client
+            .get_multiple_token_accounts(&accounts)
+            .unwrap()
+            .into_iter()
+            .map(|account| account.map(|account| account.amount))
+            .collect();
+
+        assert_eq!(amounts, [Some(100), None, Some(200)]);
+    }
+
+    #[test]
+    fn receipt_carries_transaction_details() {
+        let (owner, mint) = (Keypair::new(), Pubkey::new_unique());
+        let (source, destination) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let client = Client::new(MockBackend::new(), Keypair::new()).with_receipt_details();
+        let logs = vec![
+            format!("Program {} invoke [1]", spl_token::id()),
+            format!("Program {} success", spl_token::id()),
+        ];
+        let details = TransactionDetails {
+            fee: 5_000,
+            compute_units: Some(4_645),
+            logs: Some(logs.clone()),
+        };
+        client.set_transaction_details(1, details);
+
+        client.transfer_to(&owner, &mint, &source, &destination, 50, 6).unwrap();
+        let receipt = client.transfer_to(&owner, &mint, &source, &destination, 50, 6).unwrap();
+
+        assert_eq!(receipt.signature, client.transactions()[1].signatures[0]);
+        assert_eq!(receipt.slot, Some(1));
+        assert_eq!(receipt.fee, Some(5_000));
+        assert_eq!(receipt.compute_units, Some(4_645));
+        assert_eq!(receipt.logs, Some(logs));
     }
 }
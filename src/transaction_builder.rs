@@ -0,0 +1,437 @@
+use solana_sdk::{
+    address_lookup_table_account::AddressLookupTableAccount,
+    instruction::Instruction,
+    packet::PACKET_DATA_SIZE,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::VersionedTransaction,
+};
+use spl_token::state::{Account as TokenAccount, Mint, Multisig};
+
+use crate::{
+    backend::Backend,
+    client::compile_message,
+    compute_budget::ComputeBudget,
+    instructions::{token as instructions, InstructionSet},
+    AuthorityType, Client, ClientError, ClientResult, RpcClient, SplToken, TokenAmount, TransactionReceipt,
+};
+
+/// Collects instructions of several helper operations and sends them in as few transactions as possible.
+///
+/// Instructions of a single operation are never split between transactions.
+pub struct TransactionBuilder<'a, B = RpcClient> {
+    client: &'a Client<B>,
+    operations: Vec<InstructionSet>,
+    signers: Vec<&'a dyn Signer>,
+    keypairs: Vec<Keypair>,
+    compute_budget: ComputeBudget,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+}
+
+impl<'a, B: Backend> TransactionBuilder<'a, B> {
+    pub fn new(client: &'a Client<B>) -> Self {
+        Self {
+            client,
+            operations: Vec::new(),
+            signers: Vec::new(),
+            keypairs: Vec::new(),
+            compute_budget: client.compute_budget,
+            lookup_tables: Vec::new(),
+        }
+    }
+
+    /// Overrides the compute budget of the client for the transactions sent by this builder.
+    pub fn with_compute_budget(&mut self, compute_budget: ComputeBudget) -> &mut Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+
+    /// Sends versioned transactions compiled against the given lookup tables, which lets more operations fit into
+    /// a single transaction.
+    pub fn with_lookup_tables(&mut self, lookup_tables: Vec<AddressLookupTableAccount>) -> &mut Self {
+        self.lookup_tables = lookup_tables;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    pub fn add_instructions(
+        &mut self,
+        instructions: impl Into<InstructionSet>,
+        signers: &[&'a dyn Signer],
+    ) -> &mut Self {
+        for signer in signers {
+            self.add_signer(*signer);
+        }
+        self.operations.push(instructions.into());
+        self
+    }
+
+    pub fn add_signer(&mut self, signer: &'a dyn Signer) -> &mut Self {
+        let pubkey = signer.pubkey();
+        if pubkey != self.client.payer_pubkey() && !self.signers.iter().any(|added| added.pubkey() == pubkey) {
+            self.signers.push(signer);
+        }
+        self
+    }
+
+    pub fn create_token_mint(&mut self, owner: &Pubkey, decimals: u8) -> ClientResult<Pubkey> {
+        self.create_token_mint_with_freeze_authority(owner, None, decimals)
+    }
+
+    pub fn create_token_mint_with_freeze_authority(
+        &mut self,
+        owner: &Pubkey,
+        freeze_authority: Option<&Pubkey>,
+        decimals: u8,
+    ) -> ClientResult<Pubkey> {
+        let token_mint = Keypair::new();
+        let instructions = instructions::create_token_mint_with_freeze_authority(
+            &self.client.payer_pubkey(),
+            &token_mint.pubkey(),
+            owner,
+            freeze_authority,
+            decimals,
+            self.client.get_minimum_balance_for_rent_exemption(Mint::LEN)?,
+        )?;
+
+        Ok(self.add_keypair(instructions, token_mint))
+    }
+
+    pub fn create_multisig(&mut self, signers: &[&Pubkey], m: u8) -> ClientResult<Pubkey> {
+        let multisig = Keypair::new();
+        let instructions = instructions::create_multisig(
+            &self.client.payer_pubkey(),
+            &multisig.pubkey(),
+            signers,
+            m,
+            self.client.get_minimum_balance_for_rent_exemption(Multisig::LEN)?,
+        )?;
+
+        Ok(self.add_keypair(instructions, multisig))
+    }
+
+    pub fn create_token_account(&mut self, owner: &Pubkey, token_mint: &Pubkey) -> ClientResult<Pubkey> {
+        let lamports = self.client.get_minimum_balance_for_rent_exemption(TokenAccount::LEN)?;
+        self.create_token_account_with_lamports(owner, token_mint, lamports)
+    }
+
+    pub fn create_token_account_with_lamports(
+        &mut self,
+        owner: &Pubkey,
+        token_mint: &Pubkey,
+        lamports: u64,
+    ) -> ClientResult<Pubkey> {
+        let token_account = Keypair::new();
+        let instructions = instructions::create_token_account(
+            &self.client.payer_pubkey(),
+            &token_account.pubkey(),
+            owner,
+            token_mint,
+            lamports,
+        )?;
+
+        Ok(self.add_keypair(instructions, token_account))
+    }
+
+    pub fn mint_to(
+        &mut self,
+        owner: &'a dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<&mut Self> {
+        let instructions = instructions::mint_to(&owner.pubkey(), &[], token_mint, account, amount, decimals)?;
+        Ok(self.add_instructions(instructions, &[owner]))
+    }
+
+    pub fn transfer_to(
+        &mut self,
+        authority: &'a dyn Signer,
+        token_mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<&mut Self> {
+        let instructions = instructions::transfer_to(
+            &authority.pubkey(),
+            &[],
+            token_mint,
+            source,
+            destination,
+            amount,
+            decimals,
+        )?;
+        Ok(self.add_instructions(instructions, &[authority]))
+    }
+
+    /// Same as [`TransactionBuilder::mint_to`], but looks the decimals of the mint up.
+    pub fn mint_amount_to(
+        &mut self,
+        owner: &'a dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: TokenAmount,
+    ) -> ClientResult<&mut Self> {
+        let decimals = self.client.get_mint_decimals(token_mint)?;
+        self.mint_to(owner, token_mint, account, amount.to_units(decimals)?, decimals)
+    }
+
+    /// Same as [`TransactionBuilder::transfer_to`], but looks the decimals of the mint up.
+    pub fn transfer_amount_to(
+        &mut self,
+        authority: &'a dyn Signer,
+        token_mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        amount: TokenAmount,
+    ) -> ClientResult<&mut Self> {
+        let decimals = self.client.get_mint_decimals(token_mint)?;
+        self.transfer_to(
+            authority,
+            token_mint,
+            source,
+            destination,
+            amount.to_units(decimals)?,
+            decimals,
+        )
+    }
+
+    pub fn approve(
+        &mut self,
+        owner: &'a dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        delegate: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<&mut Self> {
+        let instructions =
+            instructions::approve(&owner.pubkey(), &[], token_mint, account, delegate, amount, decimals)?;
+        Ok(self.add_instructions(instructions, &[owner]))
+    }
+
+    pub fn revoke(&mut self, owner: &'a dyn Signer, account: &Pubkey) -> ClientResult<&mut Self> {
+        let instructions = instructions::revoke(&owner.pubkey(), &[], account)?;
+        Ok(self.add_instructions(instructions, &[owner]))
+    }
+
+    pub fn burn(
+        &mut self,
+        authority: &'a dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> ClientResult<&mut Self> {
+        let instructions = instructions::burn(&authority.pubkey(), &[], token_mint, account, amount, decimals)?;
+        Ok(self.add_instructions(instructions, &[authority]))
+    }
+
+    pub fn freeze_account(
+        &mut self,
+        freeze_authority: &'a dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<&mut Self> {
+        let instructions = instructions::freeze_account(&freeze_authority.pubkey(), &[], token_mint, account)?;
+        Ok(self.add_instructions(instructions, &[freeze_authority]))
+    }
+
+    pub fn thaw_account(
+        &mut self,
+        freeze_authority: &'a dyn Signer,
+        token_mint: &Pubkey,
+        account: &Pubkey,
+    ) -> ClientResult<&mut Self> {
+        let instructions = instructions::thaw_account(&freeze_authority.pubkey(), &[], token_mint, account)?;
+        Ok(self.add_instructions(instructions, &[freeze_authority]))
+    }
+
+    pub fn set_authority(
+        &mut self,
+        authority: &'a dyn Signer,
+        owned: &Pubkey,
+        authority_type: AuthorityType,
+        new_authority: Option<&Pubkey>,
+    ) -> ClientResult<&mut Self> {
+        let instructions = instructions::set_authority(&authority.pubkey(), &[], owned, authority_type, new_authority)?;
+        Ok(self.add_instructions(instructions, &[authority]))
+    }
+
+    pub fn create_associated_token_account(
+        &mut self,
+        funder: &'a dyn Signer,
+        recipient: &Pubkey,
+        token_mint: &Pubkey,
+    ) -> Pubkey {
+        let instructions = instructions::create_associated_token_account(&funder.pubkey(), recipient, token_mint);
+        self.add_instructions(instructions, &[funder]);
+
+        Client::<B>::get_associated_token_address(recipient, token_mint)
+    }
+
+    pub fn create_associated_token_account_by_payer(&mut self, recipient: &Pubkey, token_mint: &Pubkey) -> Pubkey {
+        self.create_associated_token_account(self.client.payer(), recipient, token_mint)
+    }
+
+    pub fn close_token_account(
+        &mut self,
+        owner: &'a dyn Signer,
+        account: &Pubkey,
+        destination: &Pubkey,
+    ) -> ClientResult<&mut Self> {
+        let instructions = instructions::close_token_account(&owner.pubkey(), &[], account, destination)?;
+        Ok(self.add_instructions(instructions, &[owner]))
+    }
+
+    /// Groups the collected operations into transactions which fit into a single packet.
+    pub fn build_batches(&self) -> Vec<Vec<Instruction>> {
+        let payer = self.client.payer_pubkey();
+        let mut batches = Vec::new();
+        let mut batch: Vec<Instruction> = Vec::new();
+
+        for operation in &self.operations {
+            let mut candidate = batch.clone();
+            candidate.extend(operation.instructions.iter().cloned());
+
+            let mut instructions = self.compute_budget.apply_placeholder(&candidate);
+            if let Some(durable_nonce) = &self.client.durable_nonce {
+                instructions = durable_nonce.apply(instructions, &payer);
+            }
+
+            let size = transaction_size(&instructions, &payer, &self.lookup_tables);
+            if !batch.is_empty() && size > PACKET_DATA_SIZE {
+                batches.push(batch);
+                batch = operation.instructions.clone();
+            } else {
+                batch = candidate;
+            }
+        }
+        if !batch.is_empty() {
+            batches.push(batch);
+        }
+
+        batches
+    }
+
+    /// Signs and sends the collected operations, returning the receipt of every sent transaction.
+    ///
+    /// Transactions are sent one by one, so when one of them fails after others were sent, the receipts of those are
+    /// returned with the error as [`ClientError::PartialBatch`].
+    pub fn send(self) -> ClientResult<Vec<TransactionReceipt>> {
+        let signers = self.all_signers().collect::<Vec<_>>();
+        let mut receipts = Vec::new();
+        for batch in self.build_batches() {
+            let result = self
+                .client
+                .prepare_transaction(&batch, &signers, &self.compute_budget, &self.lookup_tables)
+                .and_then(|transaction| {
+                    self.client
+                        .process_transaction_with_signers(&transaction, Some(&signers), self.client.confirmation)
+                });
+
+            match result {
+                Ok(receipt) => receipts.push(receipt),
+                Err(error) if receipts.is_empty() => return Err(error),
+                Err(error) => {
+                    return Err(ClientError::PartialBatch {
+                        sent: receipts,
+                        error: Box::new(error),
+                    })
+                },
+            }
+        }
+
+        Ok(receipts)
+    }
+
+    fn add_keypair(&mut self, instructions: InstructionSet, keypair: Keypair) -> Pubkey {
+        let pubkey = keypair.pubkey();
+        self.operations.push(instructions);
+        self.keypairs.push(keypair);
+        pubkey
+    }
+
+    fn all_signers(&self) -> impl Iterator<Item = &dyn Signer> {
+        self.signers
+            .iter()
+            .copied()
+            .chain(self.keypairs.iter().map(|keypair| keypair as &dyn Signer))
+    }
+}
+
+fn transaction_size(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> usize {
+    let message = match compile_message(instructions, payer, lookup_tables) {
+        Ok(message) => message,
+        Err(_) => return usize::MAX,
+    };
+    let transaction = VersionedTransaction {
+        signatures: vec![Signature::default(); usize::from(message.header().num_required_signatures)],
+        message,
+    };
+    bincode::serialized_size(&transaction)
+        .map(|size| size as usize)
+        .unwrap_or(usize::MAX)
+}
+
+impl<B: Backend> Client<B> {
+    pub fn transaction_builder(&self) -> TransactionBuilder<'_, B> {
+        TransactionBuilder::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{system_instruction, transaction::TransactionError};
+
+    use super::*;
+    use crate::mock::MockBackend;
+
+    fn transfer(client: &Client<MockBackend>) -> Vec<Instruction> {
+        let recipient = Pubkey::new_unique();
+        vec![system_instruction::transfer(&client.payer_pubkey(), &recipient, 1)]
+    }
+
+    #[test]
+    fn failed_batch_keeps_sent_receipts() {
+        let client = Client::new(MockBackend::new(), Keypair::new());
+        client.fail_transaction(1, TransactionError::AccountInUse);
+
+        let mut builder = client.transaction_builder();
+        for _ in 0..40 {
+            builder.add_instructions(transfer(&client), &[]);
+        }
+        assert!(builder.build_batches().len() > 2);
+
+        match builder.send() {
+            Err(ClientError::PartialBatch { sent, error }) => {
+                assert_eq!(sent.len(), 1);
+                assert_eq!(sent[0].signature, client.transactions()[0].signatures[0]);
+                assert!(matches!(*error, ClientError::Client(_)));
+            },
+            result => panic!("unexpected result {result:?}"),
+        }
+        assert_eq!(client.transactions().len(), 2);
+    }
+
+    #[test]
+    fn failed_first_batch_returns_its_error() {
+        let client = Client::new(MockBackend::new(), Keypair::new());
+        client.fail_transaction(0, TransactionError::AccountInUse);
+
+        let mut builder = client.transaction_builder();
+        builder.add_instructions(transfer(&client), &[]);
+
+        assert!(matches!(builder.send(), Err(ClientError::Client(_))));
+    }
+}